@@ -0,0 +1,235 @@
+use crate::{BowlingGame, FrameKind};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LeagueError {
+    /// Only finished games count towards league figures.
+    GameInProgress {
+        bowler: String,
+    },
+    EmptySeries {
+        bowler: String,
+    },
+}
+
+impl Display for LeagueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::GameInProgress { bowler } => write!(f, "{}: the game is not finished", bowler),
+            Self::EmptySeries { bowler } => write!(f, "{}: the series has no games", bowler),
+        }
+    }
+}
+
+impl std::error::Error for LeagueError {}
+
+/// What one finished game adds to a bowler's figures.
+struct GameLine {
+    score: u16,
+    strikes: usize,
+    spares: usize,
+    /// Frames that did not start with a strike.
+    spare_chances: usize,
+    frames: usize,
+}
+
+impl GameLine {
+    fn new(game: &BowlingGame) -> Option<Self> {
+        let score = game.score()?;
+        let kinds: Vec<FrameKind> = game
+            .frames
+            .iter()
+            .map(|frame| frame.kind(game.rules()))
+            .collect();
+        let count = |kind| kinds.iter().filter(|&&other| other == kind).count();
+
+        Some(Self {
+            score,
+            strikes: count(FrameKind::Strike),
+            spares: count(FrameKind::Spare),
+            spare_chances: kinds.len() - count(FrameKind::Strike),
+            frames: kinds.len(),
+        })
+    }
+}
+
+struct Bowler {
+    name: String,
+    series: Vec<Vec<GameLine>>,
+}
+
+/// A bowler's league figures to date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BowlerStats {
+    pub name: String,
+    pub games: usize,
+    pub pins: u32,
+    /// Total of each series, in the order bowled.
+    pub series: Vec<u32>,
+    /// Average after each series, dropping any fraction as leagues do.
+    pub running_average: Vec<u16>,
+    pub average: u16,
+    pub high_game: u16,
+    pub high_series: u32,
+    /// Share of frames struck, from 0 to 100.
+    pub strike_percentage: f64,
+    /// Share of the frames left open by the first ball that were spared,
+    /// from 0 to 100, or `None` if every frame was a strike.
+    pub spare_percentage: Option<f64>,
+    pub handicap: u16,
+}
+
+/// Games bowled in a league, a series of them per bowler each week. The
+/// handicap makes up `percentage` percent of the gap between a bowler's
+/// average and the `basis`, and is nothing for those averaging above it.
+pub struct League {
+    basis: u16,
+    percentage: u16,
+    bowlers: Vec<Bowler>,
+}
+
+impl Default for League {
+    /// Handicap of 90% of 220.
+    fn default() -> Self {
+        Self::with_handicap(220, 90)
+    }
+}
+
+impl League {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_handicap(basis: u16, percentage: u16) -> Self {
+        Self {
+            basis,
+            percentage,
+            bowlers: vec![],
+        }
+    }
+
+    /// Records a bowler's series, adding them to the league if they are
+    /// new. Every game must be finished.
+    pub fn add_series(&mut self, bowler: &str, games: &[BowlingGame]) -> Result<(), LeagueError> {
+        if games.is_empty() {
+            return Err(LeagueError::EmptySeries {
+                bowler: bowler.to_string(),
+            });
+        }
+
+        let series = games
+            .iter()
+            .map(GameLine::new)
+            .collect::<Option<Vec<GameLine>>>()
+            .ok_or_else(|| LeagueError::GameInProgress {
+                bowler: bowler.to_string(),
+            })?;
+
+        match self.bowlers.iter_mut().find(|other| other.name == bowler) {
+            Some(other) => other.series.push(series),
+            None => self.bowlers.push(Bowler {
+                name: bowler.to_string(),
+                series: vec![series],
+            }),
+        }
+
+        Ok(())
+    }
+
+    /// The handicap for an average.
+    pub fn handicap(&self, average: u16) -> u16 {
+        let gap = u32::from(self.basis.saturating_sub(average));
+
+        (gap * u32::from(self.percentage) / 100) as u16
+    }
+
+    pub fn stats(&self, bowler: &str) -> Option<BowlerStats> {
+        self.bowlers
+            .iter()
+            .find(|other| other.name == bowler)
+            .map(|bowler| self.stats_of(bowler))
+    }
+
+    /// Figures for every bowler, in the order they joined.
+    pub fn bowlers(&self) -> Vec<BowlerStats> {
+        self.bowlers
+            .iter()
+            .map(|bowler| self.stats_of(bowler))
+            .collect()
+    }
+
+    fn stats_of(&self, bowler: &Bowler) -> BowlerStats {
+        let lines: Vec<&GameLine> = bowler.series.iter().flatten().collect();
+        let series: Vec<u32> = bowler
+            .series
+            .iter()
+            .map(|games| games.iter().map(|game| u32::from(game.score)).sum())
+            .collect();
+
+        let mut running_average = vec![];
+        let (mut games, mut pins) = (0, 0);
+
+        for (games_in, total) in bowler.series.iter().zip(&series) {
+            games += games_in.len() as u32;
+            pins += total;
+            running_average.push((pins / games) as u16);
+        }
+
+        let sum = |count: fn(&GameLine) -> usize| lines.iter().map(|&line| count(line)).sum();
+        let percentage = |part: usize, whole: usize| match whole {
+            0 => None,
+            whole => Some(part as f64 * 100.0 / whole as f64),
+        };
+        let average = running_average.last().copied().unwrap_or(0);
+
+        BowlerStats {
+            name: bowler.name.clone(),
+            games: lines.len(),
+            pins,
+            high_game: lines.iter().map(|line| line.score).max().unwrap_or(0),
+            high_series: series.iter().copied().max().unwrap_or(0),
+            series,
+            running_average,
+            average,
+            strike_percentage: percentage(sum(|line| line.strikes), sum(|line| line.frames))
+                .unwrap_or(0.0),
+            spare_percentage: percentage(sum(|line| line.spares), sum(|line| line.spare_chances)),
+            handicap: self.handicap(average),
+        }
+    }
+
+    /// Every bowler's figures as CSV, one row each after a header. Names
+    /// are quoted when they need to be; percentages have one decimal.
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec![
+            "bowler,games,pins,average,high_game,high_series,strike_pct,spare_pct,handicap"
+                .to_string(),
+        ];
+
+        for stats in self.bowlers() {
+            lines.push(format!(
+                "{},{},{},{},{},{},{:.1},{},{}",
+                csv_field(&stats.name),
+                stats.games,
+                stats.pins,
+                stats.average,
+                stats.high_game,
+                stats.high_series,
+                stats.strike_percentage,
+                stats
+                    .spare_percentage
+                    .map_or(String::new(), |spares| format!("{:.1}", spares)),
+                stats.handicap,
+            ));
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
@@ -1,161 +1,385 @@
+pub mod league;
+pub mod notation;
+pub mod pins;
+pub mod rules;
+pub mod save;
+pub mod scoresheet;
+pub mod session;
+
+pub use rules::Rules;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error {
-    NotEnoughPinsLeft,
+    /// The roll knocked down more pins than were standing. Frames and rolls
+    /// count from one; the tenth frame's fill balls are its rolls 2 and 3.
+    NotEnoughPinsLeft {
+        frame: usize,
+        roll: usize,
+        standing: u16,
+        pins: u16,
+    },
+    /// The pin mask given to [`BowlingGame::roll_pins`] knocks down `pins`,
+    /// which were not standing.
+    PinsNotStanding {
+        frame: usize,
+        roll: usize,
+        pins: u16,
+    },
     GameComplete,
 }
 
-#[derive(PartialEq, Eq)]
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::NotEnoughPinsLeft {
+                frame,
+                roll,
+                standing,
+                pins,
+            } => write!(
+                f,
+                "frame {}, roll {}: {} pins knocked down but only {} standing",
+                frame, roll, pins, standing
+            ),
+            Self::PinsNotStanding { frame, roll, pins } => {
+                let numbers: Vec<String> = pins::numbers(*pins)
+                    .iter()
+                    .map(|pin| pin.to_string())
+                    .collect();
+
+                write!(
+                    f,
+                    "frame {}, roll {}: pins {} are not standing",
+                    frame,
+                    roll,
+                    numbers.join("-")
+                )
+            }
+            Self::GameComplete => write!(f, "the game is complete"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum FrameKind {
+    #[default]
     Open,
     Spare,
     Strike,
 }
 
-impl Default for FrameKind {
-    fn default() -> Self {
-        Self::Open
+/// Rolls of one frame; the last also holds its fill balls.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Frame {
+    /// Points knocked down by each roll, the pin count in ten-pin.
+    rolls: Vec<u16>,
+    /// Pins knocked down by each roll, when rolled with a pin mask.
+    knocked: Vec<Option<u16>>,
+}
+
+/// The rack a frame's next roll faces.
+pub(crate) struct Rack {
+    /// Points standing.
+    pub(crate) points: u16,
+    /// Pins standing, if every roll at the rack was given as a pin mask.
+    pub(crate) pins: Option<u16>,
+    /// Balls already rolled at it.
+    pub(crate) balls: usize,
+}
+
+impl Frame {
+    /// Number of balls the first rack took, once it is cleared or they have
+    /// all been rolled.
+    fn first_rack(&self, rules: &Rules) -> Option<usize> {
+        let mut standing = rules.rack_points();
+
+        for (idx, &points) in self.rolls.iter().take(rules.balls_per_frame()).enumerate() {
+            standing = standing.saturating_sub(points);
+
+            if standing == 0 || idx + 1 == rules.balls_per_frame() {
+                return Some(idx + 1);
+            }
+        }
+
+        None
+    }
+
+    fn kind(&self, rules: &Rules) -> FrameKind {
+        match self.first_rack(rules) {
+            Some(1) if self.rolls[0] == rules.rack_points() => FrameKind::Strike,
+            Some(2) if self.rolls[..2].iter().sum::<u16>() == rules.rack_points() => {
+                FrameKind::Spare
+            }
+            _ => FrameKind::Open,
+        }
+    }
+
+    /// Bonus balls the frame earns.
+    fn bonus(&self, rules: &Rules) -> usize {
+        match self.kind(rules) {
+            FrameKind::Strike => rules.strike_bonus(),
+            FrameKind::Spare => rules.spare_bonus(),
+            FrameKind::Open => 0,
+        }
+    }
+
+    fn is_done(&self, last: bool, rules: &Rules) -> bool {
+        match (self.first_rack(rules), last) {
+            (None, _) => false,
+            (Some(_), false) => true,
+            (Some(balls), true) => self.rolls.len() == balls + self.bonus(rules),
+        }
+    }
+
+    /// The rack the next roll faces. A new one is set whenever the last is
+    /// cleared or its balls are used up, which only the last frame's fill
+    /// balls ever see.
+    pub(crate) fn rack(&self, rules: &Rules) -> Rack {
+        let fresh = || Rack {
+            points: rules.rack_points(),
+            pins: Some(rules.all_pins()),
+            balls: 0,
+        };
+        let mut rack = fresh();
+
+        for (&points, &knocked) in self.rolls.iter().zip(self.knocked.iter()) {
+            rack.points -= points;
+            rack.pins = rack
+                .pins
+                .zip(knocked)
+                .map(|(pins, knocked)| pins & !knocked);
+            rack.balls += 1;
+
+            if rack.points == 0 || rack.balls == rules.balls_per_frame() {
+                rack = fresh();
+            }
+        }
+
+        rack
     }
 }
 
-#[derive(Default)]
-pub struct Frame {
-    kind: FrameKind,
-    first: u16,
-    second: u16,
-    done: bool,
+/// A frame as it stands mid-game.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FrameScore {
+    /// Points knocked down by each roll, bonus rolls included in the tenth.
+    pub rolls: Vec<u16>,
+    /// Running total up to and including this frame, once it is known.
+    pub score: Option<u16>,
+    /// The frame was a strike or a spare and its bonus rolls are not all in.
+    pub awaiting_bonus: bool,
 }
 
-#[derive(Default)]
+/// With the `serde` feature, a game is (de)serialized as its
+/// [`save::SavedGame`], and every roll is checked again on the way in.
+#[derive(Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "save::SavedGame", try_from = "save::SavedGame")
+)]
 pub struct BowlingGame {
+    rules: Rules,
     frames: Vec<Frame>,
 }
 
-const TENTH_FRAME: usize = 9;
-const MAX_PINS: u16 = 10;
-const FRAMES_COUNT: usize = 10;
+pub(crate) const TENTH_FRAME: usize = 9;
+pub(crate) const FRAMES_COUNT: usize = 10;
 
 impl BowlingGame {
+    /// A ten-pin game.
     pub fn new() -> Self {
         Self::default()
     }
 
-    fn is_game_complete(&self) -> bool {
-        let last_frame = self.frames.last();
-        let tenth_frame = self.frames.get(TENTH_FRAME);
-
-        match self.frames.len() {
-            10 => last_frame.unwrap().kind == FrameKind::Open && last_frame.unwrap().done,
-            11 => {
-                (tenth_frame.unwrap().kind == FrameKind::Strike
-                    && last_frame.unwrap().kind != FrameKind::Strike
-                    && last_frame.unwrap().done)
-                    || tenth_frame.unwrap().kind == FrameKind::Spare
-            }
-            12 => {
-                tenth_frame.unwrap().kind == FrameKind::Strike
-                    && self.frames.get(TENTH_FRAME + 1).unwrap().kind != FrameKind::Strike
-            }
-            _ => false,
+    pub fn with_rules(rules: Rules) -> Self {
+        Self {
+            rules,
+            ..Self::default()
         }
     }
 
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub(crate) fn is_game_complete(&self) -> bool {
+        self.frames.len() == FRAMES_COUNT && self.frames[TENTH_FRAME].is_done(true, &self.rules)
+    }
+
+    /// Number of frames bowled to the end, bonus rolls aside.
+    pub(crate) fn frames_done(&self) -> usize {
+        self.frames
+            .iter()
+            .enumerate()
+            .filter(|(idx, frame)| frame.is_done(*idx == TENTH_FRAME, &self.rules))
+            .count()
+    }
+
+    /// Records a roll. It is refused, and nothing is recorded, if the game
+    /// is over or more pins are knocked down than the frame has standing.
+    /// Under rules where pins are worth more than a point, such as
+    /// five-pin, `pins` is the points knocked down.
     pub fn roll(&mut self, pins: u16) -> Result<(), Error> {
-        if pins > 10 {
-            return Err(Error::NotEnoughPinsLeft);
+        self.record(pins, None)
+    }
+
+    /// Records a roll by the pins it knocked down, bit `n - 1` standing for
+    /// pin `n` (see [`pins::mask`]). Every pin knocked down must have been
+    /// standing, which is only known when the earlier rolls on the rack were
+    /// given as pin masks too.
+    pub fn roll_pins(&mut self, knocked: u16) -> Result<(), Error> {
+        self.record(self.rules.points(knocked), Some(knocked))
+    }
+
+    /// The frame the next roll goes to, empty if it is a new one.
+    fn current_frame(&self) -> Frame {
+        let last = self.frames.len() == FRAMES_COUNT;
+
+        match self.frames.last() {
+            Some(frame) if !frame.is_done(last, &self.rules) => frame.clone(),
+            _ => Frame::default(),
         }
+    }
 
+    fn record(&mut self, pins: u16, knocked: Option<u16>) -> Result<(), Error> {
         if self.is_game_complete() {
             return Err(Error::GameComplete);
         }
 
-        if self.frames.is_empty() || self.frames.last().unwrap().done {
-            self.frames.push(match pins == MAX_PINS {
-                true => Frame {
-                    kind: FrameKind::Strike,
-                    first: MAX_PINS,
-                    second: 0,
-                    done: true,
-                },
-                false => Frame {
-                    first: pins,
-                    ..Default::default()
-                },
-            });
-        } else {
-            let last_frame = self.frames.last_mut().unwrap();
+        let current = self.current_frame();
+        let frame = self.frames.len() + usize::from(current.rolls.is_empty());
+        let roll = current.rolls.len() + 1;
+        let rack = current.rack(&self.rules);
 
-            if !last_frame.done {
-                let total_pins = last_frame.first + pins;
+        let fallen = knocked.map_or(0, |knocked| {
+            knocked & !rack.pins.unwrap_or(self.rules.all_pins())
+        });
 
-                if total_pins > MAX_PINS {
-                    return Err(Error::NotEnoughPinsLeft);
-                }
+        if fallen != 0 {
+            return Err(Error::PinsNotStanding {
+                frame,
+                roll,
+                pins: fallen,
+            });
+        }
 
-                last_frame.second = pins;
-                last_frame.done = true;
+        if pins > rack.points {
+            return Err(Error::NotEnoughPinsLeft {
+                frame,
+                roll,
+                standing: rack.points,
+                pins,
+            });
+        }
 
-                if total_pins == MAX_PINS {
-                    last_frame.kind = FrameKind::Spare;
-                }
-            }
+        if current.rolls.is_empty() {
+            self.frames.push(Frame::default());
         }
 
+        let current = self.frames.last_mut().unwrap();
+        current.rolls.push(pins);
+        current.knocked.push(knocked);
+
         Ok(())
     }
 
-    fn is_score_ready(&self) -> bool {
-        let frames_count = self.frames.len();
+    /// Takes back the last roll, returning the points it knocked down, or
+    /// `None` if nothing has been rolled.
+    pub fn undo_last_roll(&mut self) -> Option<u16> {
+        let frame = self.frames.last_mut()?;
+        let pins = frame.rolls.pop()?;
+        frame.knocked.pop();
 
-        if self.frames.is_empty() || frames_count < FRAMES_COUNT {
-            return false;
+        if frame.rolls.is_empty() {
+            self.frames.pop();
         }
 
-        if frames_count < FRAMES_COUNT + 1
-            && (self.frames.get(TENTH_FRAME).unwrap().kind == FrameKind::Strike
-                || self.frames.get(TENTH_FRAME).unwrap().kind == FrameKind::Spare)
-        {
-            return false;
-        }
+        Some(pins)
+    }
+
+    /// Every roll so far, in order.
+    fn rolls(&self) -> Vec<u16> {
+        self.frames
+            .iter()
+            .flat_map(|frame| frame.rolls.iter().copied())
+            .collect()
+    }
+
+    /// The frames started so far, with running totals for those whose
+    /// bonus rolls are all in.
+    pub fn frame_scores(&self) -> Vec<FrameScore> {
+        let rolls = self.rolls();
+        let mut frames: Vec<FrameScore> = vec![];
+        let mut total = Some(0);
+        let mut start = 0;
+
+        for (idx, frame) in self.frames.iter().enumerate() {
+            let last = idx == TENTH_FRAME;
+            let done = frame.is_done(last, &self.rules);
+
+            // The last frame holds its own bonus rolls.
+            let counted = match last {
+                true => frame.rolls.len(),
+                false => frame.rolls.len() + frame.bonus(&self.rules),
+            };
+            let known = done && start + counted <= rolls.len();
+
+            total = match known {
+                true => {
+                    total.map(|total| total + rolls[start..start + counted].iter().sum::<u16>())
+                }
+                false => None,
+            };
+
+            frames.push(FrameScore {
+                rolls: frame.rolls.clone(),
+                score: total,
+                awaiting_bonus: frame.first_rack(&self.rules).is_some()
+                    && frame.bonus(&self.rules) > 0
+                    && !known,
+            });
 
-        if self.frames.get(TENTH_FRAME).unwrap().kind == FrameKind::Strike
-            && self.frames.get(TENTH_FRAME + 1).unwrap().kind == FrameKind::Strike
-            && frames_count < FRAMES_COUNT + 2
-        {
-            return false;
+            start += frame.rolls.len();
         }
 
-        true
+        frames
     }
 
     pub fn score(&self) -> Option<u16> {
-        if !self.is_score_ready() {
-            return None;
+        match self.is_game_complete() {
+            true => self.frame_scores().last().and_then(|frame| frame.score),
+            false => None,
         }
+    }
 
-        let mut score: u16 = 0;
+    /// The best final score still within reach: every ball from here on
+    /// knocks down all the pins standing.
+    pub fn max_possible_score(&self) -> u16 {
+        self.projected_score(true)
+    }
 
-        for (idx, frame) in self.frames.iter().enumerate() {
-            score += frame.first + frame.second;
-            let next = self.frames.get(idx + 1);
-
-            if frame.kind == FrameKind::Spare {
-                score += next.unwrap().first;
-            } else if frame.kind == FrameKind::Strike {
-                let next = next.unwrap();
-                score += next.first;
-                if next.kind == FrameKind::Strike {
-                    let subsequent = self.frames.get(idx + 2).unwrap();
-                    score += subsequent.first;
-                } else {
-                    score += next.second;
-                }
-            }
+    /// The final score if every ball from here on misses.
+    pub fn min_possible_score(&self) -> u16 {
+        self.projected_score(false)
+    }
 
-            if idx == 9 {
-                break;
-            }
+    fn projected_score(&self, clear: bool) -> u16 {
+        let mut game = self.clone();
+
+        while !game.is_game_complete() {
+            let pins = match clear {
+                true => game.current_frame().rack(&game.rules).points,
+                false => 0,
+            };
+
+            game.roll(pins)
+                .expect("the pins standing can be knocked down");
         }
 
-        Some(score)
+        game.score().unwrap_or(0)
     }
 }
@@ -0,0 +1,118 @@
+use crate::scoresheet::marks;
+use crate::{BowlingGame, Error, Frame, Rules, TENTH_FRAME};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum NotationError {
+    /// A space-separated token that is not the marks of one frame.
+    InvalidFrame { frame: usize, token: String },
+    /// The marks decode, but the roll is not allowed, e.g. `9X` or an
+    /// eleventh frame.
+    Roll { frame: usize, error: Error },
+}
+
+impl Display for NotationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::InvalidFrame { frame, token } => {
+                write!(f, "frame {}: `{}` is not a frame", frame, token)
+            }
+            Self::Roll { error, .. } => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+/// Points knocked down by each mark of a token, or `None` if a mark is
+/// unknown or a spare is not marked on a rack's second ball.
+fn decode(token: &str, rules: &Rules) -> Option<Vec<u16>> {
+    let mut standing = rules.rack_points();
+    let mut balls = 0;
+    let mut rolls = vec![];
+
+    for mark in token.chars() {
+        let pins = match mark {
+            'X' | 'x' => rules.rack_points(),
+            '/' if balls == 1 => standing,
+            '-' => 0,
+            _ => mark.to_digit(10).filter(|&pins| pins > 0)? as u16,
+        };
+
+        rolls.push(pins);
+        standing = standing.saturating_sub(pins);
+        balls += 1;
+
+        if standing == 0 || balls == rules.balls_per_frame() {
+            (standing, balls) = (rules.rack_points(), 0);
+        }
+    }
+
+    Some(rolls)
+}
+
+/// Builds a ten-pin game from frames written like
+/// `"X 9/ 8- X X 72 -- 9/ X XX7"`: one space-separated token per frame, `X`
+/// for a strike, `/` for a spare, `-` for a miss. The tenth frame holds its
+/// bonus rolls, though they may also be written as tokens of their own. A
+/// game in progress may end mid-frame. Every roll goes through
+/// [`BowlingGame::roll`].
+pub fn parse(notation: &str) -> Result<BowlingGame, NotationError> {
+    parse_with(notation, Rules::ten_pin())
+}
+
+/// Like [`parse`], under other rules. Counts are single digits, so a ball
+/// worth ten points or more without clearing the rack, as in five-pin,
+/// cannot be written.
+pub fn parse_with(notation: &str, rules: Rules) -> Result<BowlingGame, NotationError> {
+    let tokens: Vec<&str> = notation.split_whitespace().collect();
+    let mut game = BowlingGame::with_rules(rules);
+
+    for (idx, &token) in tokens.iter().enumerate() {
+        let frame = idx + 1;
+        let invalid = || NotationError::InvalidFrame {
+            frame,
+            token: token.to_string(),
+        };
+
+        let rolls = decode(token, game.rules()).ok_or_else(invalid)?;
+        let last = frame == tokens.len();
+
+        let fits = idx >= TENTH_FRAME || {
+            let balls = Frame {
+                knocked: vec![None; rolls.len()],
+                rolls: rolls.clone(),
+            }
+            .first_rack(game.rules());
+
+            match balls {
+                Some(balls) => balls == rolls.len(),
+                None => last,
+            }
+        };
+
+        if !fits {
+            return Err(invalid());
+        }
+
+        for pins in rolls {
+            game.roll(pins)
+                .map_err(|error| NotationError::Roll { frame, error })?;
+        }
+    }
+
+    Ok(game)
+}
+
+impl BowlingGame {
+    /// The rolls so far in the notation read by [`parse_with`].
+    pub fn notation(&self) -> String {
+        let frames: Vec<String> = self
+            .frame_scores()
+            .iter()
+            .map(|frame| marks(&frame.rolls, self.rules()).concat())
+            .collect();
+
+        frames.join(" ")
+    }
+}
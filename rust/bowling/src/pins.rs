@@ -0,0 +1,234 @@
+use crate::BowlingGame;
+
+/// Every pin of the rack, bit `n - 1` standing for pin `n`.
+pub const ALL_PINS: u16 = 0x3FF;
+const HEAD_PIN: u16 = 1;
+
+/// Where each pin stands: its row from the front, and its offset across
+/// the lane in half-pin spacings.
+const POSITIONS: [(i8, i8); 10] = [
+    (0, 0),
+    (1, -1),
+    (1, 1),
+    (2, -2),
+    (2, 0),
+    (2, 2),
+    (3, -3),
+    (3, -1),
+    (3, 1),
+    (3, 3),
+];
+
+/// Mask of the given pin numbers, 1 to 10; other numbers are ignored.
+pub fn mask(pins: &[u8]) -> u16 {
+    pins.iter()
+        .filter(|&&pin| (1..=10).contains(&pin))
+        .fold(0, |mask, &pin| mask | 1 << (pin - 1))
+}
+
+/// Pin numbers set in a mask, lowest first.
+pub fn numbers(mask: u16) -> Vec<u8> {
+    (1..=16).filter(|&pin| mask & 1 << (pin - 1) != 0).collect()
+}
+
+/// What the first ball of a rack left standing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Leave {
+    Strike,
+    /// Only the given pin is standing.
+    SinglePin(u8),
+    /// The head pin is down and the pins standing have a gap between them,
+    /// such as the 7-10, or a pin down just in front of two standing side by
+    /// side, such as the 5-6 without the 3.
+    Split,
+    /// Like a split, but with the head pin still standing, such as 1-2-10.
+    Washout,
+    /// Any other leave.
+    Other,
+}
+
+impl Leave {
+    pub fn classify(standing: u16) -> Self {
+        let pins = numbers(standing & ALL_PINS);
+
+        match pins[..] {
+            [] => return Self::Strike,
+            [pin] => return Self::SinglePin(pin),
+            _ => {}
+        }
+
+        match (has_gap(&pins), standing & HEAD_PIN != 0) {
+            (true, false) => Self::Split,
+            (true, true) => Self::Washout,
+            (false, _) => Self::Other,
+        }
+    }
+}
+
+fn position(pin: u8) -> (i8, i8) {
+    POSITIONS[pin as usize - 1]
+}
+
+fn pin_at(position: (i8, i8)) -> Option<u8> {
+    POSITIONS
+        .iter()
+        .position(|&other| other == position)
+        .map(|idx| idx as u8 + 1)
+}
+
+/// Pins close enough for one to carry the other: side by side, one just
+/// behind the other, or one straight behind the other (a sleeper).
+fn touching(a: u8, b: u8) -> bool {
+    let ((row_a, x_a), (row_b, x_b)) = (position(a), position(b));
+
+    match (row_a - row_b).abs() {
+        0 => (x_a - x_b).abs() == 2,
+        1 => (x_a - x_b).abs() == 1,
+        2 => x_a == x_b,
+        _ => false,
+    }
+}
+
+fn has_gap(pins: &[u8]) -> bool {
+    // Pins standing in separate groups.
+    let mut group = vec![pins[0]];
+    let mut idx = 0;
+
+    while idx < group.len() {
+        for &pin in pins {
+            if !group.contains(&pin) && touching(group[idx], pin) {
+                group.push(pin);
+            }
+        }
+
+        idx += 1;
+    }
+
+    if group.len() < pins.len() {
+        return true;
+    }
+
+    // A pin down just in front of and between two standing side by side.
+    pins.iter().any(|&a| {
+        pins.iter().any(|&b| {
+            let ((row_a, x_a), (row_b, x_b)) = (position(a), position(b));
+
+            row_a == row_b
+                && x_b - x_a == 2
+                && pin_at((row_a - 1, x_a + 1)).is_some_and(|ahead| !pins.contains(&ahead))
+        })
+    })
+}
+
+/// The leave of a rack's first ball, tracked by pin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LeaveRecord {
+    /// Frame number, from one.
+    pub frame: usize,
+    /// Mask of the pins left standing.
+    pub standing: u16,
+    pub leave: Leave,
+    /// Whether a later ball cleared the rack, `None` until they are rolled.
+    pub converted: Option<bool>,
+}
+
+/// How many leaves of a kind came up and how many of them were picked up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Conversion {
+    pub left: usize,
+    pub converted: usize,
+}
+
+impl Conversion {
+    /// Share of the leaves picked up, from 0 to 1.
+    pub fn rate(&self) -> Option<f64> {
+        match self.left {
+            0 => None,
+            left => Some(self.converted as f64 / left as f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LeaveStats {
+    pub splits: Conversion,
+    pub washouts: Conversion,
+    pub single_pins: Conversion,
+}
+
+impl BowlingGame {
+    /// Every leave of a first ball rolled with a pin mask, tenth-frame fill
+    /// balls included. Strikes leave nothing and are skipped. Only ten-pin
+    /// racks are classified; under other rules there are no leaves.
+    pub fn leaves(&self) -> Vec<LeaveRecord> {
+        let rules = self.rules();
+        let mut leaves = vec![];
+
+        if rules.pins() != 10 {
+            return leaves;
+        }
+
+        for (idx, frame) in self.frames.iter().enumerate() {
+            let mut start = 0;
+
+            while start < frame.rolls.len() {
+                // The balls rolled at this rack, up to the one clearing it.
+                let mut standing = rules.rack_points();
+                let mut balls = 0;
+
+                while standing > 0
+                    && balls < rules.balls_per_frame()
+                    && start + balls < frame.rolls.len()
+                {
+                    standing -= frame.rolls[start + balls];
+                    balls += 1;
+                }
+
+                if let Some(knocked) = frame.knocked[start] {
+                    let left = rules.all_pins() & !knocked;
+                    let converted = match (standing, balls) {
+                        (0, 1) => None,
+                        (0, _) => Some(true),
+                        (_, balls) if balls == rules.balls_per_frame() => Some(false),
+                        _ => None,
+                    };
+
+                    if left != 0 {
+                        leaves.push(LeaveRecord {
+                            frame: idx + 1,
+                            standing: left,
+                            leave: Leave::classify(left),
+                            converted,
+                        });
+                    }
+                }
+
+                start += balls;
+            }
+        }
+
+        leaves
+    }
+
+    /// Splits, washouts and single pins left so far, and how many were
+    /// converted. Leaves still waiting on their last ball are not counted.
+    pub fn leave_stats(&self) -> LeaveStats {
+        let mut stats = LeaveStats::default();
+
+        for record in self.leaves() {
+            let conversion = match record.leave {
+                Leave::Split => &mut stats.splits,
+                Leave::Washout => &mut stats.washouts,
+                Leave::SinglePin(_) => &mut stats.single_pins,
+                Leave::Strike | Leave::Other => continue,
+            };
+
+            if let Some(converted) = record.converted {
+                conversion.left += 1;
+                conversion.converted += usize::from(converted);
+            }
+        }
+
+        stats
+    }
+}
@@ -0,0 +1,142 @@
+use crate::FRAMES_COUNT;
+
+/// Most bonus balls a strike or spare may earn.
+pub const MAX_BONUS: usize = 3;
+/// Most balls a frame may allow.
+pub const MAX_BALLS: usize = 10;
+
+/// Variant of the game: how many balls are rolled at each rack, the pins in
+/// it and what each is worth, and the bonus balls a strike or spare earns.
+///
+/// A strike clears the rack with the first ball and a spare with the
+/// second. Clearing it with a later ball, a "ten-box" in candlepin, earns
+/// no bonus. Only the last frame rolls on after a strike or spare: its fill
+/// balls start from a fresh rack, each one facing what the one before left
+/// standing unless it cleared the rack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    balls_per_frame: usize,
+    pin_values: Vec<u16>,
+    strike_bonus: usize,
+    spare_bonus: usize,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::ten_pin()
+    }
+}
+
+impl Rules {
+    /// Rules of your own. `pin_values` holds the points of pins 1, 2, ... and
+    /// sets how many there are, from 1 to 16. `None` if a pin is worth
+    /// nothing, a perfect game would score more than a `u16` holds, the
+    /// balls per frame are not from 1 to [`MAX_BALLS`], or a bonus is over
+    /// [`MAX_BONUS`] balls.
+    pub fn new(
+        balls_per_frame: usize,
+        pin_values: &[u16],
+        strike_bonus: usize,
+        spare_bonus: usize,
+    ) -> Option<Self> {
+        // A frame scores at most a rack, and a rack more per bonus ball.
+        let rack: u64 = pin_values.iter().map(|&value| u64::from(value)).sum();
+        let balls = 1 + strike_bonus.max(spare_bonus).min(MAX_BONUS) as u64;
+        let best = rack * FRAMES_COUNT as u64 * balls;
+
+        let valid = (1..=MAX_BALLS).contains(&balls_per_frame)
+            && (1..=16).contains(&pin_values.len())
+            && pin_values.iter().all(|&value| value > 0)
+            && strike_bonus <= MAX_BONUS
+            && spare_bonus <= MAX_BONUS
+            && best <= u64::from(u16::MAX);
+
+        match valid {
+            true => Some(Self {
+                balls_per_frame,
+                pin_values: pin_values.to_vec(),
+                strike_bonus,
+                spare_bonus,
+            }),
+            false => None,
+        }
+    }
+
+    pub fn ten_pin() -> Self {
+        Self::new(2, &[1; 10], 2, 1).unwrap()
+    }
+
+    /// Thin pins, three balls a frame, fallen wood left on the lane.
+    ///
+    /// Candlepin and duckpin score alike, so their rules are equal and a
+    /// game, saved or not, does not tell which of the two was bowled.
+    pub fn candlepin() -> Self {
+        Self::new(3, &[1; 10], 2, 1).unwrap()
+    }
+
+    /// Small pins and balls, three balls a frame. The same rules as
+    /// [`Rules::candlepin`].
+    pub fn duckpin() -> Self {
+        Self::new(3, &[1; 10], 2, 1).unwrap()
+    }
+
+    /// Nine pins set in a diamond, scored in frames like ten-pin with two
+    /// balls each, for 270 points in a perfect game.
+    pub fn nine_pin() -> Self {
+        Self::new(2, &[1; 9], 2, 1).unwrap()
+    }
+
+    /// Canadian five-pin: three balls a frame at pins worth 2-3-5-3-2, from
+    /// left to right, for 15 points a rack.
+    pub fn five_pin() -> Self {
+        Self::new(3, &[2, 3, 5, 3, 2], 2, 1).unwrap()
+    }
+
+    pub fn balls_per_frame(&self) -> usize {
+        self.balls_per_frame
+    }
+
+    /// Number of pins in a rack.
+    pub fn pins(&self) -> usize {
+        self.pin_values.len()
+    }
+
+    pub fn pin_values(&self) -> &[u16] {
+        &self.pin_values
+    }
+
+    pub fn strike_bonus(&self) -> usize {
+        self.strike_bonus
+    }
+
+    pub fn spare_bonus(&self) -> usize {
+        self.spare_bonus
+    }
+
+    /// Points for clearing a rack.
+    pub fn rack_points(&self) -> u16 {
+        self.pin_values.iter().sum()
+    }
+
+    /// Mask of every pin in a rack.
+    pub fn all_pins(&self) -> u16 {
+        (1u32 << self.pins()).wrapping_sub(1) as u16
+    }
+
+    /// Points for the pins set in `mask`.
+    pub fn points(&self, mask: u16) -> u16 {
+        self.pin_values
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| mask & 1 << idx != 0)
+            .map(|(_, &value)| value)
+            .sum()
+    }
+
+    /// Balls a frame may take at most, fill balls included.
+    pub(crate) fn last_frame_balls(&self) -> usize {
+        self.balls_per_frame
+            .max(1 + self.strike_bonus)
+            .max(2 + self.spare_bonus)
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{BowlingGame, Error, Rules};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Version written by [`BowlingGame::save`], bumped whenever the layout of
+/// [`SavedGame`] changes.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LoadError {
+    /// Written by a newer or unknown version of the format.
+    UnsupportedVersion(u32),
+    InvalidRules,
+    /// A roll is not allowed where it falls; rolls count from one.
+    Roll {
+        roll: usize,
+        error: Error,
+    },
+    /// The points recorded for a roll are not what its pins are worth.
+    PointsMismatch {
+        roll: usize,
+        points: u16,
+        pins: u16,
+    },
+    /// The text is not a saved game. Only `from_json`, with the `serde`
+    /// feature, reads text.
+    Json(String),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::UnsupportedVersion(version) => {
+                write!(f, "format version {} is not supported", version)
+            }
+            Self::InvalidRules => write!(f, "the rules are not valid"),
+            Self::Roll { roll, error } => write!(f, "roll {}: {}", roll, error),
+            Self::PointsMismatch { roll, points, pins } => {
+                let numbers: Vec<String> = crate::pins::numbers(*pins)
+                    .iter()
+                    .map(|pin| pin.to_string())
+                    .collect();
+
+                write!(
+                    f,
+                    "roll {}: {} points recorded for pins {}",
+                    roll,
+                    points,
+                    numbers.join("-")
+                )
+            }
+            Self::Json(message) => write!(f, "not a saved game: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedRules {
+    pub balls_per_frame: usize,
+    pub pin_values: Vec<u16>,
+    pub strike_bonus: usize,
+    pub spare_bonus: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedRoll {
+    pub points: u16,
+    /// Mask of the pins knocked down, when the roll was given as one.
+    pub pins: Option<u16>,
+}
+
+/// A game as it is stored: the rules and every roll in order. Loading plays
+/// the rolls again, so a game that could not have been bowled is refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedGame {
+    pub version: u32,
+    pub rules: SavedRules,
+    pub rolls: Vec<SavedRoll>,
+}
+
+impl SavedGame {
+    pub fn load(&self) -> Result<BowlingGame, LoadError> {
+        if self.version != FORMAT_VERSION {
+            return Err(LoadError::UnsupportedVersion(self.version));
+        }
+
+        let rules = &self.rules;
+        let rules = Rules::new(
+            rules.balls_per_frame,
+            &rules.pin_values,
+            rules.strike_bonus,
+            rules.spare_bonus,
+        )
+        .ok_or(LoadError::InvalidRules)?;
+        let mut game = BowlingGame::with_rules(rules);
+
+        for (idx, saved) in self.rolls.iter().enumerate() {
+            let roll = idx + 1;
+
+            let rolled = match saved.pins {
+                Some(pins) if game.rules().points(pins) != saved.points => {
+                    return Err(LoadError::PointsMismatch {
+                        roll,
+                        points: saved.points,
+                        pins,
+                    })
+                }
+                Some(pins) => game.roll_pins(pins),
+                None => game.roll(saved.points),
+            };
+
+            rolled.map_err(|error| LoadError::Roll { roll, error })?;
+        }
+
+        Ok(game)
+    }
+}
+
+impl BowlingGame {
+    /// The game in the current [`FORMAT_VERSION`], ready to be stored.
+    pub fn save(&self) -> SavedGame {
+        let rules = self.rules();
+
+        SavedGame {
+            version: FORMAT_VERSION,
+            rules: SavedRules {
+                balls_per_frame: rules.balls_per_frame(),
+                pin_values: rules.pin_values().to_vec(),
+                strike_bonus: rules.strike_bonus(),
+                spare_bonus: rules.spare_bonus(),
+            },
+            rolls: self
+                .frames
+                .iter()
+                .flat_map(|frame| frame.rolls.iter().zip(frame.knocked.iter()))
+                .map(|(&points, &pins)| SavedRoll { points, pins })
+                .collect(),
+        }
+    }
+}
+
+impl From<BowlingGame> for SavedGame {
+    fn from(game: BowlingGame) -> Self {
+        game.save()
+    }
+}
+
+impl TryFrom<SavedGame> for BowlingGame {
+    type Error = LoadError;
+
+    fn try_from(saved: SavedGame) -> Result<Self, Self::Error> {
+        saved.load()
+    }
+}
+
+/// The game as JSON, e.g. for a game of one gutter ball:
+///
+/// ```text
+/// {"version":1,"rules":{"balls_per_frame":2,"pin_values":[1,1,1,1,1,1,1,1,1,1],
+/// "strike_bonus":2,"spare_bonus":1},"rolls":[{"points":0,"pins":null}]}
+/// ```
+#[cfg(feature = "serde")]
+pub fn to_json(game: &BowlingGame) -> String {
+    serde_json::to_string(&game.save()).expect("a saved game is plain data")
+}
+
+/// Reads a game written by [`to_json`], checking every roll.
+#[cfg(feature = "serde")]
+pub fn from_json(json: &str) -> Result<BowlingGame, LoadError> {
+    serde_json::from_str::<SavedGame>(json)
+        .map_err(|error| LoadError::Json(error.to_string()))?
+        .load()
+}
@@ -0,0 +1,110 @@
+use crate::{BowlingGame, Rules, FRAMES_COUNT, TENTH_FRAME};
+
+/// Scoresheet marks for the rolls of one frame: `X` for a strike, `/` for
+/// a spare, `-` for a miss and the points knocked down otherwise. A fresh
+/// rack is set after it is cleared or its balls are used up, as in the
+/// tenth frame.
+pub(crate) fn marks(rolls: &[u16], rules: &Rules) -> Vec<String> {
+    let mut standing = rules.rack_points();
+    let mut balls = 0;
+    let mut marks = vec![];
+
+    for &pins in rolls {
+        marks.push(match pins {
+            _ if pins == standing && balls == 0 => "X".to_string(),
+            _ if pins == standing && balls == 1 => "/".to_string(),
+            0 => "-".to_string(),
+            _ => pins.to_string(),
+        });
+
+        standing -= pins;
+        balls += 1;
+
+        if standing == 0 || balls == rules.balls_per_frame() {
+            (standing, balls) = (rules.rack_points(), 0);
+        }
+    }
+
+    marks
+}
+
+/// Boxes drawn for a frame, the last one holding its fill balls too.
+fn box_count(idx: usize, rules: &Rules) -> usize {
+    match idx == TENTH_FRAME {
+        true => rules.last_frame_balls(),
+        false => rules.balls_per_frame(),
+    }
+}
+
+/// Width of a box, enough for the most points a ball can knock down
+/// without clearing the rack.
+fn box_width(rules: &Rules) -> usize {
+    (rules.rack_points() - 1).max(1).to_string().len()
+}
+
+fn border(rules: &Rules) -> String {
+    let cells: Vec<String> = (0..FRAMES_COUNT)
+        .map(|idx| "-".repeat(width(idx, rules)))
+        .collect();
+
+    format!("+{}+", cells.join("+"))
+}
+
+fn width(idx: usize, rules: &Rules) -> usize {
+    box_count(idx, rules) * (box_width(rules) + 1) + 1
+}
+
+fn row(cells: impl Iterator<Item = String>) -> String {
+    let cells: Vec<String> = cells.collect();
+
+    format!("|{}|", cells.join("|"))
+}
+
+/// Draws the classic scoresheet: frame numbers, the marks in each frame's
+/// boxes (three in the tenth of ten-pin) and the running total once it is known.
+///
+/// ```text
+/// +-----+-----+-----+-----+-----+-----+-----+-----+-----+-------+
+/// |  1  |  2  |  3  |  4  |  5  |  6  |  7  |  8  |  9  |  10   |
+/// |   X | 9 / | 8 - |   X |     |     |     |     |     |       |
+/// |  20 |  38 |  46 |     |     |     |     |     |     |       |
+/// +-----+-----+-----+-----+-----+-----+-----+-----+-----+-------+
+/// ```
+pub fn render(game: &BowlingGame) -> String {
+    let rules = game.rules();
+    let frames = game.frame_scores();
+
+    let header =
+        row((0..FRAMES_COUNT).map(|idx| format!("{:^w$}", idx + 1, w = width(idx, rules))));
+
+    let boxes = row((0..FRAMES_COUNT).map(|idx| {
+        let mut marks = frames
+            .get(idx)
+            .map_or(vec![], |frame| marks(&frame.rolls, rules));
+        let count = box_count(idx, rules);
+
+        // A strike goes in the right-hand box.
+        if idx != TENTH_FRAME && marks == ["X"] {
+            marks.splice(0..0, vec![String::new(); count - 1]);
+        }
+
+        marks.resize(count, String::new());
+
+        let marks: Vec<String> = marks
+            .iter()
+            .map(|mark| format!("{:>w$}", mark, w = box_width(rules)))
+            .collect();
+        format!(" {} ", marks.join(" "))
+    }));
+
+    let totals = row((0..FRAMES_COUNT).map(|idx| {
+        let total = frames
+            .get(idx)
+            .and_then(|frame| frame.score)
+            .map_or(String::new(), |score| score.to_string());
+
+        format!("{:>w$} ", total, w = width(idx, rules) - 1)
+    }));
+
+    [border(rules), header, boxes, totals, border(rules)].join("\n")
+}
@@ -0,0 +1,227 @@
+use crate::{BowlingGame, Error, Rules};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SessionError {
+    /// Two players cannot share a name.
+    DuplicatePlayer(String),
+    UnknownPlayer(String),
+    /// Players join before the first roll of the session.
+    AlreadyStarted,
+    /// The named player is not up; `expected` is.
+    NotYourTurn {
+        player: String,
+        expected: String,
+    },
+    /// Every player present has finished.
+    SessionComplete,
+    Roll {
+        player: String,
+        error: Error,
+    },
+}
+
+impl Display for SessionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::DuplicatePlayer(name) => write!(f, "{} is already playing", name),
+            Self::UnknownPlayer(name) => write!(f, "{} is not playing", name),
+            Self::AlreadyStarted => write!(f, "the session has started"),
+            Self::NotYourTurn { player, expected } => {
+                write!(f, "it is {}'s turn, not {}'s", expected, player)
+            }
+            Self::SessionComplete => write!(f, "every player has finished"),
+            Self::Roll { player, error } => write!(f, "{}: {}", player, error),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+struct Player {
+    name: String,
+    game: BowlingGame,
+    /// Score counted instead of the game when the player is absent.
+    blind: Option<u16>,
+}
+
+impl Player {
+    fn is_up(&self) -> bool {
+        self.blind.is_none() && !self.game.is_game_complete()
+    }
+}
+
+/// A player's place, best first. Tied players share a place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub place: usize,
+    pub name: String,
+    /// Running total so far, or the blind score.
+    pub score: u16,
+    pub blind: bool,
+    pub finished: bool,
+}
+
+/// Several players bowling in rotation: each bowls a whole frame before the
+/// next player is up, in the order they joined.
+#[derive(Default)]
+pub struct Session {
+    rules: Rules,
+    players: Vec<Player>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A session whose players all bowl under `rules`.
+    pub fn with_rules(rules: Rules) -> Self {
+        Self {
+            rules,
+            ..Self::default()
+        }
+    }
+
+    pub fn add_player(&mut self, name: &str) -> Result<(), SessionError> {
+        if self.players.iter().any(|player| player.name == name) {
+            return Err(SessionError::DuplicatePlayer(name.to_string()));
+        }
+
+        if self.has_started() {
+            return Err(SessionError::AlreadyStarted);
+        }
+
+        self.players.push(Player {
+            name: name.to_string(),
+            game: BowlingGame::with_rules(self.rules.clone()),
+            blind: None,
+        });
+
+        Ok(())
+    }
+
+    /// Takes an absent player out of the rotation, counting `blind` as their
+    /// score instead of anything they have bowled.
+    pub fn mark_absent(&mut self, name: &str, blind: u16) -> Result<(), SessionError> {
+        let player = self.player_mut(name)?;
+        player.blind = Some(blind);
+
+        Ok(())
+    }
+
+    /// Puts an absent player back in the rotation with the game they had.
+    pub fn mark_present(&mut self, name: &str) -> Result<(), SessionError> {
+        let player = self.player_mut(name)?;
+        player.blind = None;
+
+        Ok(())
+    }
+
+    fn player_mut(&mut self, name: &str) -> Result<&mut Player, SessionError> {
+        self.players
+            .iter_mut()
+            .find(|player| player.name == name)
+            .ok_or_else(|| SessionError::UnknownPlayer(name.to_string()))
+    }
+
+    fn has_started(&self) -> bool {
+        self.players
+            .iter()
+            .any(|player| !player.game.frame_scores().is_empty())
+    }
+
+    fn current(&self) -> Option<usize> {
+        let frame = self
+            .players
+            .iter()
+            .filter(|player| player.is_up())
+            .map(|player| player.game.frames_done())
+            .min()?;
+
+        self.players
+            .iter()
+            .position(|player| player.is_up() && player.game.frames_done() == frame)
+    }
+
+    /// The player up next, or `None` once everyone present has finished.
+    pub fn current_player(&self) -> Option<&str> {
+        self.current().map(|idx| self.players[idx].name.as_str())
+    }
+
+    /// Rolls for the player who is up.
+    pub fn roll(&mut self, pins: u16) -> Result<(), SessionError> {
+        let idx = self.current().ok_or(SessionError::SessionComplete)?;
+        let player = &mut self.players[idx];
+
+        player.game.roll(pins).map_err(|error| SessionError::Roll {
+            player: player.name.clone(),
+            error,
+        })
+    }
+
+    /// Rolls for the named player, refused if they are not up.
+    pub fn roll_for(&mut self, name: &str, pins: u16) -> Result<(), SessionError> {
+        let current = self.current_player().ok_or(SessionError::SessionComplete)?;
+
+        if current != name {
+            return Err(
+                match self.players.iter().any(|player| player.name == name) {
+                    true => SessionError::NotYourTurn {
+                        player: name.to_string(),
+                        expected: current.to_string(),
+                    },
+                    false => SessionError::UnknownPlayer(name.to_string()),
+                },
+            );
+        }
+
+        self.roll(pins)
+    }
+
+    pub fn game(&self, name: &str) -> Option<&BowlingGame> {
+        self.players
+            .iter()
+            .find(|player| player.name == name)
+            .map(|player| &player.game)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.current().is_none()
+    }
+
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = self
+            .players
+            .iter()
+            .map(|player| Standing {
+                place: 0,
+                name: player.name.clone(),
+                score: player.blind.unwrap_or_else(|| {
+                    let frames = player.game.frame_scores();
+                    frames
+                        .iter()
+                        .rev()
+                        .find_map(|frame| frame.score)
+                        .unwrap_or(0)
+                }),
+                blind: player.blind.is_some(),
+                finished: player.blind.is_some() || player.game.is_game_complete(),
+            })
+            .collect();
+
+        standings.sort_by_key(|standing| std::cmp::Reverse(standing.score));
+
+        let mut last = None;
+
+        for (idx, standing) in standings.iter_mut().enumerate() {
+            if last.is_none_or(|(score, _)| score != standing.score) {
+                last = Some((standing.score, idx + 1));
+            }
+
+            standing.place = last.unwrap().1;
+        }
+
+        standings
+    }
+}
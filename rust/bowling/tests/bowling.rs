@@ -13,7 +13,15 @@ mod tests {
     fn you_cannot_roll_more_than_ten_pins_in_a_single_roll() {
         let mut game = BowlingGame::new();
 
-        assert_eq!(game.roll(11), Err(Error::NotEnoughPinsLeft));
+        assert_eq!(
+            game.roll(11),
+            Err(Error::NotEnoughPinsLeft {
+                frame: 1,
+                roll: 1,
+                standing: 10,
+                pins: 11
+            })
+        );
     }
 
     #[test]
@@ -264,7 +272,15 @@ mod tests {
         let mut game = BowlingGame::new();
 
         assert!(game.roll(5).is_ok());
-        assert_eq!(game.roll(6), Err(Error::NotEnoughPinsLeft));
+        assert_eq!(
+            game.roll(6),
+            Err(Error::NotEnoughPinsLeft {
+                frame: 1,
+                roll: 2,
+                standing: 5,
+                pins: 6
+            })
+        );
     }
 
     #[test]
@@ -277,7 +293,15 @@ mod tests {
 
         let _ = game.roll(10);
 
-        assert_eq!(game.roll(11), Err(Error::NotEnoughPinsLeft));
+        assert_eq!(
+            game.roll(11),
+            Err(Error::NotEnoughPinsLeft {
+                frame: 10,
+                roll: 2,
+                standing: 10,
+                pins: 11
+            })
+        );
     }
 
     #[test]
@@ -291,7 +315,15 @@ mod tests {
         let _ = game.roll(10);
 
         assert!(game.roll(5).is_ok());
-        assert_eq!(game.roll(6), Err(Error::NotEnoughPinsLeft));
+        assert_eq!(
+            game.roll(6),
+            Err(Error::NotEnoughPinsLeft {
+                frame: 10,
+                roll: 3,
+                standing: 5,
+                pins: 6
+            })
+        );
     }
 
     #[test]
@@ -319,7 +351,15 @@ mod tests {
         let _ = game.roll(10);
 
         assert!(game.roll(6).is_ok());
-        assert_eq!(game.roll(10), Err(Error::NotEnoughPinsLeft));
+        assert_eq!(
+            game.roll(10),
+            Err(Error::NotEnoughPinsLeft {
+                frame: 10,
+                roll: 3,
+                standing: 4,
+                pins: 10
+            })
+        );
     }
 
     #[test]
@@ -334,7 +374,15 @@ mod tests {
         let _ = game.roll(10);
 
         assert!(game.roll(10).is_ok());
-        assert_eq!(game.roll(11), Err(Error::NotEnoughPinsLeft));
+        assert_eq!(
+            game.roll(11),
+            Err(Error::NotEnoughPinsLeft {
+                frame: 10,
+                roll: 3,
+                standing: 10,
+                pins: 11
+            })
+        );
     }
 
     #[test]
@@ -419,4 +467,986 @@ mod tests {
 
         assert_eq!(game.score(), Some(31));
     }
+
+    fn game_with(rolls: &[u16]) -> BowlingGame {
+        game_under(Rules::ten_pin(), rolls)
+    }
+
+    fn game_under(rules: Rules, rolls: &[u16]) -> BowlingGame {
+        let mut game = BowlingGame::with_rules(rules);
+
+        for &pins in rolls {
+            game.roll(pins).unwrap();
+        }
+
+        game
+    }
+
+    #[test]
+    fn frame_scores_are_empty_before_the_first_roll() {
+        assert_eq!(BowlingGame::new().frame_scores(), vec![]);
+    }
+
+    #[test]
+    fn frame_scores_track_a_game_in_progress() {
+        let game = game_with(&[10, 9, 1, 8, 0, 10, 3]);
+
+        assert_eq!(
+            game.frame_scores(),
+            vec![
+                FrameScore {
+                    rolls: vec![10],
+                    score: Some(20),
+                    awaiting_bonus: false,
+                },
+                FrameScore {
+                    rolls: vec![9, 1],
+                    score: Some(38),
+                    awaiting_bonus: false,
+                },
+                FrameScore {
+                    rolls: vec![8, 0],
+                    score: Some(46),
+                    awaiting_bonus: false,
+                },
+                FrameScore {
+                    rolls: vec![10],
+                    score: None,
+                    awaiting_bonus: true,
+                },
+                FrameScore {
+                    rolls: vec![3],
+                    score: None,
+                    awaiting_bonus: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn frame_scores_wait_for_a_spare_bonus() {
+        let frames = game_with(&[4, 6]).frame_scores();
+
+        assert_eq!(frames[0].score, None);
+        assert!(frames[0].awaiting_bonus);
+    }
+
+    #[test]
+    fn tenth_frame_scores_include_the_bonus_rolls() {
+        let mut game = game_with(&[0; 18]);
+        game.roll(10).unwrap();
+
+        let tenth = game.frame_scores()[9].clone();
+        assert_eq!(tenth.rolls, vec![10]);
+        assert!(tenth.awaiting_bonus);
+
+        game.roll(10).unwrap();
+        game.roll(7).unwrap();
+
+        let frames = game.frame_scores();
+        assert_eq!(frames.len(), 10);
+        assert_eq!(
+            frames[9],
+            FrameScore {
+                rolls: vec![10, 10, 7],
+                score: Some(27),
+                awaiting_bonus: false,
+            }
+        );
+        assert_eq!(frames[9].score, game.score());
+    }
+
+    #[test]
+    fn scoresheet_of_a_game_in_progress() {
+        let game = game_with(&[10, 9, 1, 8, 0, 10, 3]);
+
+        assert_eq!(
+            scoresheet::render(&game),
+            "\
++-----+-----+-----+-----+-----+-----+-----+-----+-----+-------+
+|  1  |  2  |  3  |  4  |  5  |  6  |  7  |  8  |  9  |  10   |
+|   X | 9 / | 8 - |   X | 3   |     |     |     |     |       |
+|  20 |  38 |  46 |     |     |     |     |     |     |       |
++-----+-----+-----+-----+-----+-----+-----+-----+-----+-------+"
+        );
+    }
+
+    #[test]
+    fn scoresheet_of_a_finished_game() {
+        let game = game_with(&[
+            0, 0, 0, 10, 1, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 10, 9, 1,
+        ]);
+
+        assert_eq!(
+            scoresheet::render(&game),
+            "\
++-----+-----+-----+-----+-----+-----+-----+-----+-----+-------+
+|  1  |  2  |  3  |  4  |  5  |  6  |  7  |  8  |  9  |  10   |
+| - - | - / | 1 2 | - - | - - | - - | - - | - - | - - | X 9 / |
+|   0 |  11 |  14 |  14 |  14 |  14 |  14 |  14 |  14 |    34 |
++-----+-----+-----+-----+-----+-----+-----+-----+-----+-------+"
+        );
+    }
+
+    fn round_trip(text: &str) -> BowlingGame {
+        let game = notation::parse(text).unwrap();
+        assert_eq!(game.notation(), text);
+
+        game
+    }
+
+    #[test]
+    fn notation_round_trips_a_perfect_game() {
+        assert_eq!(round_trip("X X X X X X X X X XXX").score(), Some(300));
+    }
+
+    #[test]
+    fn notation_round_trips_all_spares() {
+        assert_eq!(
+            round_trip("5/ 5/ 5/ 5/ 5/ 5/ 5/ 5/ 5/ 5/5").score(),
+            Some(150)
+        );
+    }
+
+    #[test]
+    fn notation_round_trips_a_gutter_game() {
+        assert_eq!(round_trip("-- -- -- -- -- -- -- -- -- --").score(), Some(0));
+    }
+
+    #[test]
+    fn notation_round_trips_a_mixed_game() {
+        let game = round_trip("X 9/ 8- X X 72 -- 9/ X X9/");
+
+        assert_eq!(game.score(), Some(170));
+    }
+
+    #[test]
+    fn notation_round_trips_a_game_in_progress() {
+        let game = round_trip("X 9/ 8");
+
+        assert_eq!(game.frame_scores().len(), 3);
+        assert_eq!(game.score(), None);
+    }
+
+    #[test]
+    fn notation_accepts_lowercase_and_split_bonus_rolls() {
+        let game = notation::parse("x x x x x x x x x x x x").unwrap();
+
+        assert_eq!(game.notation(), "X X X X X X X X X XXX");
+    }
+
+    #[test]
+    fn notation_rejects_tokens_that_are_not_one_frame() {
+        for (text, frame, token) in [
+            ("X 9/ /1", 3, "/1"),
+            ("X 9/ 8a", 3, "8a"),
+            ("X5 9/", 1, "X5"),
+            ("X 9/8 X", 2, "9/8"),
+            ("7 X", 1, "7"),
+        ] {
+            assert_eq!(
+                notation::parse(text).err(),
+                Some(notation::NotationError::InvalidFrame {
+                    frame,
+                    token: token.to_string()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn notation_validates_each_roll() {
+        assert!(matches!(
+            notation::parse("X 9X"),
+            Err(notation::NotationError::Roll {
+                frame: 2,
+                error: Error::NotEnoughPinsLeft { .. }
+            })
+        ));
+        assert!(matches!(
+            notation::parse("-- -- -- -- -- -- -- -- -- -- X"),
+            Err(notation::NotationError::Roll {
+                frame: 11,
+                error: Error::GameComplete
+            })
+        ));
+    }
+
+    #[test]
+    fn errors_say_which_frame_and_roll_failed() {
+        let mut game = game_with(&[10, 3]);
+        let err = game.roll(8).unwrap_err();
+
+        assert_eq!(
+            err,
+            Error::NotEnoughPinsLeft {
+                frame: 2,
+                roll: 2,
+                standing: 7,
+                pins: 8
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "frame 2, roll 2: 8 pins knocked down but only 7 standing"
+        );
+    }
+
+    #[test]
+    fn a_refused_roll_leaves_the_game_untouched() {
+        let mut game = game_with(&[10, 9, 1]);
+
+        assert!(game.roll(11).is_err());
+        assert_eq!(game.frame_scores().len(), 2);
+        assert!(game.roll(7).is_ok());
+        assert_eq!(game.notation(), "X 9/ 7");
+    }
+
+    #[test]
+    fn the_fill_ball_after_a_final_spare_has_a_fresh_rack() {
+        let mut game = game_with(&[0; 18]);
+        game.roll(3).unwrap();
+        game.roll(7).unwrap();
+
+        assert!(game.roll(10).is_ok());
+        assert_eq!(game.score(), Some(20));
+        assert_eq!(game.roll(0), Err(Error::GameComplete));
+    }
+
+    #[test]
+    fn the_third_strike_in_the_tenth_ends_the_game() {
+        let mut game = game_with(&[0; 18]);
+
+        for _ in 0..3 {
+            game.roll(10).unwrap();
+        }
+
+        assert_eq!(game.roll(0), Err(Error::GameComplete));
+        assert_eq!(game.score(), Some(30));
+    }
+
+    #[test]
+    fn leaves_are_classified() {
+        let leave = |standing: &[u8]| pins::Leave::classify(pins::mask(standing));
+
+        assert_eq!(leave(&[]), pins::Leave::Strike);
+        assert_eq!(leave(&[10]), pins::Leave::SinglePin(10));
+        assert_eq!(leave(&[7, 10]), pins::Leave::Split);
+        assert_eq!(leave(&[4, 6, 7, 10]), pins::Leave::Split);
+        assert_eq!(leave(&[3, 10]), pins::Leave::Split);
+        assert_eq!(leave(&[5, 6]), pins::Leave::Split);
+        assert_eq!(leave(&[1, 2, 10]), pins::Leave::Washout);
+        assert_eq!(leave(&[1, 2, 4, 10]), pins::Leave::Washout);
+        assert_eq!(leave(&[2, 8]), pins::Leave::Other);
+        assert_eq!(leave(&[3, 5, 6]), pins::Leave::Other);
+        assert_eq!(leave(&[1, 2, 4, 7]), pins::Leave::Other);
+    }
+
+    #[test]
+    fn rolling_by_pins_scores_like_counts() {
+        let mut game = BowlingGame::new();
+
+        game.roll_pins(pins::ALL_PINS & !pins::mask(&[7, 10]))
+            .unwrap();
+        game.roll_pins(pins::mask(&[7, 10])).unwrap();
+        game.roll_pins(pins::ALL_PINS).unwrap();
+
+        assert_eq!(game.notation(), "8/ X");
+        assert_eq!(game.frame_scores()[0].score, Some(20));
+    }
+
+    #[test]
+    fn rolling_by_pins_only_knocks_down_standing_pins() {
+        let mut game = BowlingGame::new();
+        game.roll_pins(pins::ALL_PINS & !pins::mask(&[7, 10]))
+            .unwrap();
+
+        let err = game.roll_pins(pins::mask(&[6, 7])).unwrap_err();
+
+        assert_eq!(
+            err,
+            Error::PinsNotStanding {
+                frame: 1,
+                roll: 2,
+                pins: pins::mask(&[6])
+            }
+        );
+        assert_eq!(err.to_string(), "frame 1, roll 2: pins 6 are not standing");
+        assert_eq!(
+            game.roll_pins(1 << 12),
+            Err(Error::PinsNotStanding {
+                frame: 1,
+                roll: 2,
+                pins: 1 << 12
+            })
+        );
+        assert!(game.roll_pins(pins::mask(&[7])).is_ok());
+    }
+
+    #[test]
+    fn rolling_by_pins_after_a_count_checks_the_count() {
+        let mut game = BowlingGame::new();
+        game.roll(8).unwrap();
+
+        assert!(matches!(
+            game.roll_pins(pins::mask(&[1, 2, 3])),
+            Err(Error::NotEnoughPinsLeft { .. })
+        ));
+        assert!(game.roll_pins(pins::mask(&[1, 2])).is_ok());
+        assert_eq!(game.leaves(), vec![]);
+    }
+
+    #[test]
+    fn leave_stats_count_conversions() {
+        let mut game = BowlingGame::new();
+        let knock = |standing: &[u8]| pins::ALL_PINS & !pins::mask(standing);
+
+        // 7-10 missed, 5-6 converted, single pin converted, strike.
+        for (first, second) in [
+            (knock(&[7, 10]), pins::mask(&[7])),
+            (knock(&[5, 6]), pins::mask(&[5, 6])),
+            (knock(&[10]), pins::mask(&[10])),
+        ] {
+            game.roll_pins(first).unwrap();
+            game.roll_pins(second).unwrap();
+        }
+
+        game.roll_pins(pins::ALL_PINS).unwrap();
+        game.roll_pins(knock(&[1, 2, 10])).unwrap();
+
+        let leaves = game.leaves();
+        assert_eq!(leaves.len(), 4);
+        assert_eq!(
+            leaves[3],
+            pins::LeaveRecord {
+                frame: 5,
+                standing: pins::mask(&[1, 2, 10]),
+                leave: pins::Leave::Washout,
+                converted: None,
+            }
+        );
+
+        let stats = game.leave_stats();
+        assert_eq!(
+            stats.splits,
+            pins::Conversion {
+                left: 2,
+                converted: 1
+            }
+        );
+        assert_eq!(stats.splits.rate(), Some(0.5));
+        assert_eq!(stats.single_pins.rate(), Some(1.0));
+        assert_eq!(stats.washouts.rate(), None);
+    }
+
+    #[test]
+    fn leaves_include_tenth_frame_fill_balls() {
+        let mut game = game_with(&[0; 18]);
+        game.roll_pins(pins::ALL_PINS).unwrap();
+        game.roll_pins(pins::ALL_PINS & !pins::mask(&[4, 6, 7, 10]))
+            .unwrap();
+        game.roll_pins(pins::mask(&[4, 6, 7, 10])).unwrap();
+
+        assert_eq!(game.score(), Some(20));
+        assert_eq!(
+            game.leave_stats().splits,
+            pins::Conversion {
+                left: 1,
+                converted: 1
+            }
+        );
+    }
+
+    fn session_with(names: &[&str]) -> session::Session {
+        let mut session = session::Session::new();
+
+        for name in names {
+            session.add_player(name).unwrap();
+        }
+
+        session
+    }
+
+    #[test]
+    fn session_players_take_turns_by_frame() {
+        let mut session = session_with(&["Ann", "Bob"]);
+
+        assert_eq!(session.current_player(), Some("Ann"));
+        session.roll(3).unwrap();
+        assert_eq!(session.current_player(), Some("Ann"));
+        session.roll(4).unwrap();
+        assert_eq!(session.current_player(), Some("Bob"));
+        session.roll(10).unwrap();
+        assert_eq!(session.current_player(), Some("Ann"));
+
+        assert_eq!(
+            session.roll_for("Bob", 5),
+            Err(session::SessionError::NotYourTurn {
+                player: "Bob".to_string(),
+                expected: "Ann".to_string()
+            })
+        );
+        assert_eq!(
+            session.roll_for("Cy", 5),
+            Err(session::SessionError::UnknownPlayer("Cy".to_string()))
+        );
+        assert!(session.roll_for("Ann", 5).is_ok());
+    }
+
+    #[test]
+    fn session_players_join_before_the_first_roll() {
+        let mut session = session_with(&["Ann"]);
+
+        assert_eq!(
+            session.add_player("Ann"),
+            Err(session::SessionError::DuplicatePlayer("Ann".to_string()))
+        );
+
+        session.roll(3).unwrap();
+
+        assert_eq!(
+            session.add_player("Bob"),
+            Err(session::SessionError::AlreadyStarted)
+        );
+    }
+
+    #[test]
+    fn session_tenth_frame_keeps_the_player_up_for_fill_balls() {
+        let mut session = session_with(&["Ann", "Bob"]);
+
+        for _ in 0..9 {
+            for _ in 0..4 {
+                session.roll(0).unwrap();
+            }
+        }
+
+        session.roll(10).unwrap();
+        assert_eq!(session.current_player(), Some("Ann"));
+        session.roll(10).unwrap();
+        session.roll(10).unwrap();
+        assert_eq!(session.current_player(), Some("Bob"));
+        session.roll(1).unwrap();
+        session.roll(2).unwrap();
+
+        assert!(session.is_complete());
+        assert_eq!(session.current_player(), None);
+        assert_eq!(session.roll(0), Err(session::SessionError::SessionComplete));
+        assert_eq!(session.game("Ann").unwrap().score(), Some(30));
+    }
+
+    #[test]
+    fn session_absent_players_are_skipped_and_score_blind() {
+        let mut session = session_with(&["Ann", "Bob", "Cy"]);
+        session.mark_absent("Bob", 120).unwrap();
+
+        session.roll(10).unwrap();
+        assert_eq!(session.current_player(), Some("Cy"));
+
+        assert_eq!(
+            session.mark_absent("Dee", 100),
+            Err(session::SessionError::UnknownPlayer("Dee".to_string()))
+        );
+
+        session.roll_for("Cy", 0).unwrap();
+        session.roll_for("Cy", 0).unwrap();
+
+        for frame in 2..=10 {
+            session.roll_for("Ann", 10).unwrap();
+
+            if frame == 10 {
+                session.roll_for("Ann", 10).unwrap();
+                session.roll_for("Ann", 10).unwrap();
+            }
+
+            session.roll_for("Cy", 0).unwrap();
+            session.roll_for("Cy", 0).unwrap();
+        }
+
+        assert!(session.is_complete());
+        assert_eq!(
+            session.standings(),
+            vec![
+                session::Standing {
+                    place: 1,
+                    name: "Ann".to_string(),
+                    score: 300,
+                    blind: false,
+                    finished: true,
+                },
+                session::Standing {
+                    place: 2,
+                    name: "Bob".to_string(),
+                    score: 120,
+                    blind: true,
+                    finished: true,
+                },
+                session::Standing {
+                    place: 3,
+                    name: "Cy".to_string(),
+                    score: 0,
+                    blind: false,
+                    finished: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn session_standings_mid_game_share_tied_places() {
+        let mut session = session_with(&["Ann", "Bob", "Cy"]);
+
+        for pins in [4, 5, 9, 0, 10] {
+            session.roll(pins).unwrap();
+        }
+
+        let standings = session.standings();
+        let places: Vec<(usize, &str, u16)> = standings
+            .iter()
+            .map(|standing| (standing.place, standing.name.as_str(), standing.score))
+            .collect();
+
+        assert_eq!(places, vec![(1, "Ann", 9), (1, "Bob", 9), (3, "Cy", 0)]);
+        assert!(standings.iter().all(|standing| !standing.finished));
+    }
+
+    #[test]
+    fn rules_need_balls_and_pins_worth_something() {
+        assert!(Rules::new(2, &[1; 10], 2, 1).is_some());
+        assert_eq!(Rules::new(2, &[u16::MAX, 1], 2, 1), None);
+        assert_eq!(Rules::new(2, &[1; 10], rules::MAX_BONUS + 1, 1), None);
+        assert_eq!(Rules::new(2, &[300; 10], 2, 1), None);
+        let heavy = Rules::new(2, &[200; 10], 2, 1).unwrap();
+        assert_eq!(BowlingGame::with_rules(heavy).max_possible_score(), 60000);
+        assert_eq!(Rules::new(usize::MAX, &[1; 10], 2, 1), None);
+        assert_eq!(Rules::new(rules::MAX_BALLS + 1, &[1; 10], 2, 1), None);
+        assert_eq!(Rules::new(0, &[1; 10], 2, 1), None);
+        assert_eq!(Rules::new(2, &[], 2, 1), None);
+        assert_eq!(Rules::new(2, &[1, 0, 1], 2, 1), None);
+        assert_eq!(Rules::new(2, &[1; 17], 2, 1), None);
+        assert_eq!(Rules::default(), Rules::ten_pin());
+    }
+
+    #[test]
+    fn candlepin_frames_take_three_balls() {
+        let mut rolls = vec![3, 4, 2];
+        rolls.extend([0; 27]);
+
+        let game = game_under(Rules::candlepin(), &rolls);
+        assert_eq!(game.frame_scores()[0].rolls, vec![3, 4, 2]);
+        assert_eq!(game.score(), Some(9));
+    }
+
+    #[test]
+    fn candlepin_ten_box_earns_no_bonus() {
+        let mut rolls = vec![3, 4, 3, 5, 0, 0];
+        rolls.extend([0; 24]);
+
+        let game = game_under(Rules::candlepin(), &rolls);
+        assert_eq!(game.frame_scores()[0].score, Some(10));
+        assert_eq!(game.score(), Some(15));
+    }
+
+    #[test]
+    fn candlepin_strikes_and_spares_end_the_frame_early() {
+        let mut game = game_under(Rules::candlepin(), &[10, 6, 4, 5, 2]);
+
+        let frames = game.frame_scores();
+        assert_eq!(frames[0].score, Some(20));
+        assert_eq!(frames[1].score, Some(35));
+        assert_eq!(frames[2].score, None);
+
+        assert_eq!(
+            game.roll(4),
+            Err(Error::NotEnoughPinsLeft {
+                frame: 3,
+                roll: 3,
+                standing: 3,
+                pins: 4
+            })
+        );
+    }
+
+    #[test]
+    fn five_pin_pins_have_values() {
+        let mut game = BowlingGame::with_rules(Rules::five_pin());
+
+        game.roll_pins(pins::mask(&[3])).unwrap();
+        game.roll_pins(pins::mask(&[1, 2])).unwrap();
+
+        assert_eq!(game.frame_scores()[0].rolls, vec![5, 5]);
+        assert_eq!(
+            game.roll_pins(pins::mask(&[3])),
+            Err(Error::PinsNotStanding {
+                frame: 1,
+                roll: 3,
+                pins: pins::mask(&[3])
+            })
+        );
+        assert_eq!(
+            game.roll(6),
+            Err(Error::NotEnoughPinsLeft {
+                frame: 1,
+                roll: 3,
+                standing: 5,
+                pins: 6
+            })
+        );
+        assert!(game.leaves().is_empty());
+    }
+
+    #[test]
+    fn nine_pin_racks_hold_nine_pins() {
+        let mut game = BowlingGame::with_rules(Rules::nine_pin());
+
+        assert_eq!(
+            game.roll(10),
+            Err(Error::NotEnoughPinsLeft {
+                frame: 1,
+                roll: 1,
+                standing: 9,
+                pins: 10
+            })
+        );
+        assert!(game
+            .roll_pins(pins::mask(&[1, 2, 3, 4, 5, 6, 7, 8, 9]))
+            .is_ok());
+        assert_eq!(game.frame_scores()[0].rolls, vec![9]);
+        assert_eq!(game.max_possible_score(), 270);
+        assert!(game.leaves().is_empty());
+    }
+
+    #[test]
+    fn five_pin_perfect_game() {
+        let game = game_under(Rules::five_pin(), &[15; 12]);
+        assert_eq!(game.score(), Some(450));
+    }
+
+    #[test]
+    fn candlepin_notation_round_trips() {
+        let text = "X 9/ 72- 334 X X X X X XXX";
+        let game = notation::parse_with(text, Rules::candlepin()).unwrap();
+
+        assert_eq!(game.notation(), text);
+        assert_eq!(game.score(), Some(236));
+    }
+
+    #[test]
+    fn scoresheet_widens_boxes_for_five_pin() {
+        let game = game_under(Rules::five_pin(), &[15, 5, 10, 2]);
+
+        assert_eq!(
+            scoresheet::render(&game),
+            "\
++----------+----------+----------+----------+----------+----------+----------+----------+----------+----------+
+|    1     |    2     |    3     |    4     |    5     |    6     |    7     |    8     |    9     |    10    |
+|        X |  5  /    |  2       |          |          |          |          |          |          |          |
+|       30 |       47 |          |          |          |          |          |          |          |          |
++----------+----------+----------+----------+----------+----------+----------+----------+----------+----------+"
+        );
+    }
+
+    #[test]
+    fn session_players_bowl_under_its_rules() {
+        let mut session = session::Session::with_rules(Rules::candlepin());
+        session.add_player("Ann").unwrap();
+
+        for pins in [3, 4, 2] {
+            session.roll(pins).unwrap();
+        }
+
+        let game = session.game("Ann").unwrap();
+        assert_eq!(game.rules(), &Rules::candlepin());
+        assert_eq!(game.frame_scores()[0].rolls, vec![3, 4, 2]);
+    }
+
+    fn league_games() -> [BowlingGame; 3] {
+        [
+            game_with(&[10; 12]),
+            game_with(&[5; 21]),
+            game_with(&[0; 20]),
+        ]
+    }
+
+    #[test]
+    fn league_tracks_series_and_running_average() {
+        let mut league = league::League::new();
+        league.add_series("Ann", &league_games()).unwrap();
+        league.add_series("Ann", &[game_with(&[5; 21])]).unwrap();
+
+        let stats = league.stats("Ann").unwrap();
+        assert_eq!(stats.games, 4);
+        assert_eq!(stats.pins, 600);
+        assert_eq!(stats.series, vec![450, 150]);
+        assert_eq!(stats.running_average, vec![150, 150]);
+        assert_eq!(stats.average, 150);
+        assert_eq!(stats.high_game, 300);
+        assert_eq!(stats.high_series, 450);
+        assert_eq!(stats.strike_percentage, 25.0);
+        assert_eq!(stats.spare_percentage, Some(20.0 * 100.0 / 30.0));
+        assert_eq!(stats.handicap, 63);
+        assert_eq!(league.stats("Bob"), None);
+    }
+
+    #[test]
+    fn league_handicap_is_configurable() {
+        let league = league::League::with_handicap(200, 80);
+
+        assert_eq!(league.handicap(150), 40);
+        assert_eq!(league.handicap(199), 0);
+        assert_eq!(league.handicap(230), 0);
+    }
+
+    #[test]
+    fn league_takes_only_finished_games() {
+        let mut league = league::League::new();
+
+        assert_eq!(
+            league.add_series("Ann", &[game_with(&[10; 12]), game_with(&[10])]),
+            Err(league::LeagueError::GameInProgress {
+                bowler: "Ann".to_string()
+            })
+        );
+        assert_eq!(
+            league.add_series("Ann", &[]),
+            Err(league::LeagueError::EmptySeries {
+                bowler: "Ann".to_string()
+            })
+        );
+        assert!(league.bowlers().is_empty());
+    }
+
+    #[test]
+    fn league_exports_csv() {
+        let mut league = league::League::new();
+        league.add_series("Ann", &league_games()).unwrap();
+        league
+            .add_series("Smith, \"Bob\"", &[game_with(&[10; 12])])
+            .unwrap();
+
+        assert_eq!(
+            league.to_csv(),
+            "\
+bowler,games,pins,average,high_game,high_series,strike_pct,spare_pct,handicap
+Ann,3,450,150,300,450,33.3,50.0,63
+\"Smith, \"\"Bob\"\"\",1,300,300,300,300,100.0,,0
+"
+        );
+    }
+
+    #[test]
+    fn possible_scores_of_a_new_game() {
+        let game = BowlingGame::new();
+
+        assert_eq!(game.max_possible_score(), 300);
+        assert_eq!(game.min_possible_score(), 0);
+    }
+
+    #[test]
+    fn possible_scores_mid_frame() {
+        let game = game_with(&[9]);
+        assert_eq!(game.max_possible_score(), 290);
+        assert_eq!(game.min_possible_score(), 9);
+
+        let game = game_with(&[10, 3]);
+        assert_eq!(game.max_possible_score(), 280);
+        assert_eq!(game.min_possible_score(), 16);
+    }
+
+    #[test]
+    fn possible_scores_of_a_finished_game_are_its_score() {
+        let game = game_with(&[10, 9, 1, 8, 0, 10, 3, 4, 0, 0, 0, 0, 0, 0, 0, 0, 10, 10, 7]);
+
+        assert_eq!(game.max_possible_score(), 97);
+        assert_eq!(game.min_possible_score(), 97);
+        assert_eq!(game.score(), Some(97));
+    }
+
+    #[test]
+    fn possible_scores_follow_the_rules() {
+        let game = BowlingGame::with_rules(Rules::five_pin());
+        assert_eq!(game.max_possible_score(), 450);
+
+        let game = game_under(Rules::candlepin(), &[3, 4]);
+        assert_eq!(game.max_possible_score(), 280);
+        assert_eq!(game.min_possible_score(), 7);
+    }
+
+    #[test]
+    fn undo_takes_back_rolls_across_frames() {
+        let mut game = game_with(&[10, 3]);
+
+        assert_eq!(game.undo_last_roll(), Some(3));
+        assert_eq!(game.frame_scores().len(), 1);
+        assert_eq!(game.undo_last_roll(), Some(10));
+        assert_eq!(game.undo_last_roll(), None);
+        assert!(game.frame_scores().is_empty());
+    }
+
+    #[test]
+    fn undo_reopens_a_finished_game() {
+        let mut game = game_with(&[10; 12]);
+
+        assert_eq!(game.undo_last_roll(), Some(10));
+        assert_eq!(game.score(), None);
+        assert!(game.roll(9).is_ok());
+        assert_eq!(game.score(), Some(299));
+    }
+
+    #[test]
+    fn undo_restores_the_pins_standing() {
+        let mut game = BowlingGame::new();
+        game.roll_pins(pins::mask(&[1, 2, 3])).unwrap();
+        game.roll_pins(pins::mask(&[4])).unwrap();
+
+        assert_eq!(game.undo_last_roll(), Some(1));
+        assert!(game.roll_pins(pins::mask(&[4, 5])).is_ok());
+        assert_eq!(game.frame_scores()[0].rolls, vec![3, 2]);
+    }
+
+    #[test]
+    fn saved_games_load_back() {
+        let mut game = BowlingGame::with_rules(Rules::candlepin());
+        game.roll_pins(pins::mask(&[1, 2, 3])).unwrap();
+        game.roll(4).unwrap();
+
+        let saved = game.save();
+        assert_eq!(saved.version, save::FORMAT_VERSION);
+        assert_eq!(
+            saved.rolls,
+            vec![
+                save::SavedRoll {
+                    points: 3,
+                    pins: Some(pins::mask(&[1, 2, 3]))
+                },
+                save::SavedRoll {
+                    points: 4,
+                    pins: None
+                },
+            ]
+        );
+
+        let loaded = saved.load().unwrap();
+        assert_eq!(loaded.rules(), &Rules::candlepin());
+        assert_eq!(loaded.save(), saved);
+        assert_eq!(loaded.frame_scores(), game.frame_scores());
+    }
+
+    #[test]
+    fn loading_replays_every_roll() {
+        let mut saved = game_with(&[10, 9, 1]).save();
+        saved.rolls[2].points = 5;
+
+        assert_eq!(
+            saved.load().err(),
+            Some(save::LoadError::Roll {
+                roll: 3,
+                error: Error::NotEnoughPinsLeft {
+                    frame: 2,
+                    roll: 2,
+                    standing: 1,
+                    pins: 5
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn loading_rejects_corrupted_saves() {
+        let game = game_with(&[3]);
+
+        let mut saved = game.save();
+        saved.version = 2;
+        assert_eq!(
+            saved.load().err(),
+            Some(save::LoadError::UnsupportedVersion(2))
+        );
+
+        let mut saved = game.save();
+        saved.rules.balls_per_frame = 0;
+        assert_eq!(saved.load().err(), Some(save::LoadError::InvalidRules));
+
+        let mut saved = game.save();
+        saved.rules.pin_values = vec![40000, 40000];
+        assert_eq!(saved.load().err(), Some(save::LoadError::InvalidRules));
+
+        // Racks fit a `u16`, but twelve strikes would not.
+        let mut saved = game.save();
+        saved.rules.pin_values = vec![300; 10];
+        saved.rolls = vec![
+            save::SavedRoll {
+                points: 3000,
+                pins: None
+            };
+            12
+        ];
+        assert_eq!(saved.load().err(), Some(save::LoadError::InvalidRules));
+
+        let mut saved = game.save();
+        saved.rules.balls_per_frame = usize::MAX;
+        assert_eq!(saved.load().err(), Some(save::LoadError::InvalidRules));
+
+        let mut saved = game.save();
+        saved.rules.strike_bonus = usize::MAX;
+        assert_eq!(saved.load().err(), Some(save::LoadError::InvalidRules));
+
+        let mut saved = game.save();
+        saved.rules.spare_bonus = usize::MAX;
+        assert_eq!(saved.load().err(), Some(save::LoadError::InvalidRules));
+
+        let mut saved = game.save();
+        saved.rolls[0].pins = Some(pins::mask(&[1, 2]));
+        let error = saved.load().err().unwrap();
+        assert_eq!(
+            error,
+            save::LoadError::PointsMismatch {
+                roll: 1,
+                points: 3,
+                pins: pins::mask(&[1, 2])
+            }
+        );
+        assert_eq!(error.to_string(), "roll 1: 3 points recorded for pins 1-2");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn games_round_trip_through_json() {
+        let game = game_with(&[0]);
+        let json = save::to_json(&game);
+
+        assert_eq!(
+            json,
+            r#"{"version":1,"rules":{"balls_per_frame":2,"pin_values":[1,1,1,1,1,1,1,1,1,1],"strike_bonus":2,"spare_bonus":1},"rolls":[{"points":0,"pins":null}]}"#
+        );
+        assert_eq!(save::from_json(&json).unwrap().save(), game.save());
+
+        let game: BowlingGame = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&game).unwrap(), json);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn corrupted_json_is_rejected() {
+        assert!(matches!(
+            save::from_json("{\"version\":1"),
+            Err(save::LoadError::Json(_))
+        ));
+
+        let json =
+            save::to_json(&game_with(&[0])).replace("[1,1,1,1,1,1,1,1,1,1]", "[40000,40000]");
+        assert_eq!(
+            save::from_json(&json).err(),
+            Some(save::LoadError::InvalidRules)
+        );
+
+        let json =
+            save::to_json(&game_with(&[10; 12])).replace("]}", ",{\"points\":1,\"pins\":null}]}");
+        assert_eq!(
+            save::from_json(&json).err(),
+            Some(save::LoadError::Roll {
+                roll: 13,
+                error: Error::GameComplete
+            })
+        );
+        assert!(serde_json::from_str::<BowlingGame>(&json).is_err());
+    }
 }
@@ -0,0 +1,71 @@
+//! Throughput of the lookup-table evaluator against [`poker::evaluate`] over
+//! every five-card hand. Run with `cargo bench`. The hands are written out
+//! before timing, but `evaluate` still parses each one.
+use poker::deck::Deck;
+use poker::lookup::{self, PackedCard};
+use poker::Card;
+use std::hint::black_box;
+use std::time::Instant;
+
+fn hands<T: Copy>(cards: &[T]) -> Vec<[T; 5]> {
+    let mut hands = vec![];
+
+    for a in 0..cards.len() {
+        for b in a + 1..cards.len() {
+            for c in b + 1..cards.len() {
+                for d in c + 1..cards.len() {
+                    for e in d + 1..cards.len() {
+                        hands.push([cards[a], cards[b], cards[c], cards[d], cards[e]]);
+                    }
+                }
+            }
+        }
+    }
+
+    hands
+}
+
+fn report(name: &str, count: usize, start: Instant) {
+    let seconds = start.elapsed().as_secs_f64();
+
+    println!(
+        "{:<8} {} hands in {:.3}s, {:.1}M hands/s",
+        name,
+        count,
+        seconds,
+        count as f64 / seconds / 1e6
+    );
+}
+
+fn main() {
+    let cards: Vec<Card> = Deck::new().cards().to_vec();
+    let packed: Vec<PackedCard> = cards
+        .iter()
+        .map(|&card| PackedCard::try_from(card).unwrap())
+        .collect();
+
+    // Builds the tables outside of the timed loop.
+    lookup::classes();
+
+    let packed_hands = hands(&packed);
+    let start = Instant::now();
+    for hand in packed_hands.iter() {
+        black_box(lookup::evaluate(black_box(hand)));
+    }
+    report("lookup", packed_hands.len(), start);
+
+    let text_hands: Vec<String> = hands(&cards)
+        .iter()
+        .map(|hand| {
+            hand.iter()
+                .map(|card| card.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    let start = Instant::now();
+    for hand in text_hands.iter() {
+        black_box(poker::evaluate(black_box(hand)));
+    }
+    report("naive", text_hands.len(), start);
+}
@@ -0,0 +1,155 @@
+use crate::{Card, SUITS};
+
+/// Small seedable generator (SplitMix64), enough for shuffling and sampling
+/// reproducibly without pulling in a dependency.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform index in `0..n`.
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// Community cards dealt on each street, each after a burn card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Street {
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    pub fn cards(&self) -> usize {
+        match self {
+            Self::Flop => 3,
+            Self::Turn | Self::River => 1,
+        }
+    }
+}
+
+/// A deck without jokers, full or short. Cards are dealt from the end of
+/// [`Deck::cards`], the top of the deck.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deck {
+    cards: Vec<Card>,
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deck {
+    /// Full deck ordered by suit, then from the deuce up to the ace.
+    pub fn new() -> Self {
+        Self::from_value(2)
+    }
+
+    /// Short deck for 6+ hold'em: the deuces through fives are removed.
+    pub fn short() -> Self {
+        Self::from_value(6)
+    }
+
+    fn from_value(lowest: u8) -> Self {
+        Self {
+            cards: SUITS
+                .iter()
+                .flat_map(|&kind| (lowest..=14).map(move |value| Card { kind, value }))
+                .collect(),
+        }
+    }
+
+    /// Shuffles the remaining cards; the same seed always gives the same order.
+    pub fn shuffle(&mut self, seed: u64) {
+        let mut rng = Rng::new(seed);
+
+        for idx in (1..self.cards.len()).rev() {
+            self.cards.swap(idx, rng.below(idx + 1));
+        }
+    }
+
+    /// Deals the top card.
+    pub fn deal(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    /// Discards the top card face down.
+    pub fn burn(&mut self) -> Option<Card> {
+        self.deal()
+    }
+
+    /// Deals `count` cards to each of `seats` players, one card at a time
+    /// around the table. Leaves the deck untouched if it runs short.
+    pub fn deal_seats(&mut self, seats: usize, count: usize) -> Option<Vec<Vec<Card>>> {
+        if seats * count > self.len() {
+            return None;
+        }
+
+        let mut hands = vec![vec![]; seats];
+
+        for _ in 0..count {
+            for hand in hands.iter_mut() {
+                hand.push(self.deal()?);
+            }
+        }
+
+        Some(hands)
+    }
+
+    /// Burns a card, then deals the community cards of `street`. Leaves the
+    /// deck untouched if it runs short.
+    pub fn deal_street(&mut self, street: Street) -> Option<Vec<Card>> {
+        if street.cards() + 1 > self.len() {
+            return None;
+        }
+
+        self.burn()?;
+        (0..street.cards()).map(|_| self.deal()).collect()
+    }
+
+    /// Takes the given cards out of the deck, ignoring any it does not hold.
+    pub fn remove(&mut self, cards: &[Card]) {
+        self.cards.retain(|card| !cards.contains(card));
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Draws `count` distinct random cards, leaving the deck untouched.
+    pub(crate) fn sample(&self, count: usize, rng: &mut Rng) -> Vec<Card> {
+        let mut cards = self.cards.clone();
+
+        for idx in 0..count {
+            let pick = idx + rng.below(cards.len() - idx);
+            cards.swap(idx, pick);
+        }
+
+        cards.truncate(count);
+        cards
+    }
+}
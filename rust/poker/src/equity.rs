@@ -0,0 +1,319 @@
+use crate::deck::{Deck, Rng};
+use crate::holdem::parse_hole;
+use crate::{
+    best_five_with, combinations, parse_cards, Card, HandParseError, HandValue, RankingRules,
+};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+const HOLE_CARDS: usize = 2;
+const BOARD_CARDS: usize = 5;
+
+/// Runouts above this count are sampled instead of enumerated.
+pub const EXHAUSTIVE_LIMIT: u64 = 20_000;
+pub const DEFAULT_TRIALS: usize = 10_000;
+
+/// How the remaining board cards are dealt out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Every possible runout, once.
+    Exhaustive,
+    /// `trials` random runouts drawn from a generator seeded with `seed`.
+    MonteCarlo { trials: usize, seed: u64 },
+}
+
+/// A player's share of the runouts, in percent.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Equity {
+    /// Runouts won outright.
+    pub win: f64,
+    /// Runouts where the pot is split.
+    pub tie: f64,
+    /// Expected share of the pot, split pots counting for their fraction.
+    pub equity: f64,
+}
+
+/// Why the equity of a deal cannot be worked out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquityError {
+    Parse(HandParseError),
+    /// The board needs more cards than the deck has left, e.g. when there
+    /// are too many players.
+    NotEnoughCards {
+        needed: usize,
+        left: usize,
+    },
+}
+
+impl Display for EquityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Parse(err) => write!(f, "{}", err),
+            Self::NotEnoughCards { needed, left } => {
+                write!(f, "{} cards are needed but {} are left", needed, left)
+            }
+        }
+    }
+}
+
+impl Error for EquityError {}
+
+impl From<HandParseError> for EquityError {
+    fn from(err: HandParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+pub(crate) struct Deal {
+    holes: Vec<Vec<Card>>,
+    board: Vec<Card>,
+    deck: Deck,
+    rules: RankingRules,
+}
+
+impl Deal {
+    fn parse(
+        players: &[&str],
+        board: &str,
+        dead: &str,
+        rules: RankingRules,
+    ) -> Result<Self, EquityError> {
+        let holes = players
+            .iter()
+            .map(|hole| parse_hole(hole, &[HOLE_CARDS]))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let board_cards = parse_cards(board)?;
+
+        if board_cards.len() > BOARD_CARDS {
+            return Err(HandParseError::WrongCardCount {
+                hand: board.to_string(),
+                count: board_cards.len(),
+                expected: BOARD_CARDS,
+            }
+            .into());
+        }
+
+        let dead_cards = parse_cards(dead)?;
+
+        let mut sources: Vec<(&str, &[Card])> = players
+            .iter()
+            .zip(holes.iter())
+            .map(|(&hole, cards)| (hole, cards.as_slice()))
+            .collect();
+        sources.push((board, &board_cards));
+        sources.push((dead, &dead_cards));
+
+        let mut seen: Vec<Card> = vec![];
+
+        for (text, cards) in sources {
+            rules.check(text, cards)?;
+
+            for (position, card) in cards.iter().enumerate() {
+                if seen.contains(card) {
+                    return Err(HandParseError::DuplicateCard {
+                        token: text.split_whitespace().nth(position).unwrap().to_string(),
+                        position,
+                    }
+                    .into());
+                }
+
+                seen.push(*card);
+            }
+        }
+
+        let deal = Self::new(holes, board_cards, &dead_cards, rules);
+
+        if deal.deck.len() < deal.missing() {
+            return Err(EquityError::NotEnoughCards {
+                needed: deal.missing(),
+                left: deal.deck.len(),
+            });
+        }
+
+        Ok(deal)
+    }
+
+    /// Deal of cards already known not to collide, from the short deck
+    /// under [`RankingRules::ShortDeck`] and the full one otherwise.
+    pub(crate) fn new(
+        holes: Vec<Vec<Card>>,
+        board: Vec<Card>,
+        dead: &[Card],
+        rules: RankingRules,
+    ) -> Self {
+        let mut deck = match rules {
+            RankingRules::ShortDeck => Deck::short(),
+            _ => Deck::new(),
+        };
+        deck.remove(&board);
+        deck.remove(dead);
+
+        for hole in holes.iter() {
+            deck.remove(hole);
+        }
+
+        Self {
+            holes,
+            board,
+            deck,
+            rules,
+        }
+    }
+
+    fn missing(&self) -> usize {
+        BOARD_CARDS - self.board.len()
+    }
+
+    pub(crate) fn runouts(&self) -> u64 {
+        let (n, k) = (self.deck.len() as u64, self.missing() as u64);
+
+        (0..k).fold(1, |count, i| count * (n - i) / (i + 1))
+    }
+
+    fn run(&self, method: Method) -> Vec<Equity> {
+        let mut tally = Tally::new(self.holes.len());
+        self.run_into(method, &mut tally);
+
+        tally.equities()
+    }
+
+    pub(crate) fn run_into(&self, method: Method, tally: &mut Tally) {
+        match method {
+            Method::Exhaustive => {
+                for runout in combinations(self.deck.cards(), self.missing()) {
+                    tally.add(&self.values(&runout));
+                }
+            }
+            Method::MonteCarlo { trials, seed } => {
+                let mut rng = Rng::new(seed);
+
+                for _ in 0..trials {
+                    let runout = self.deck.sample(self.missing(), &mut rng);
+                    tally.add(&self.values(&runout));
+                }
+            }
+        }
+    }
+
+    fn values(&self, runout: &[Card]) -> Vec<HandValue> {
+        self.holes
+            .iter()
+            .map(|hole| {
+                let mut cards = hole.clone();
+                cards.extend(&self.board);
+                cards.extend(runout);
+
+                best_five_with(&cards, self.rules).value
+            })
+            .collect()
+    }
+}
+
+pub(crate) struct Tally {
+    runs: u64,
+    wins: Vec<u64>,
+    ties: Vec<u64>,
+    shares: Vec<f64>,
+}
+
+impl Tally {
+    pub(crate) fn new(players: usize) -> Self {
+        Self {
+            runs: 0,
+            wins: vec![0; players],
+            ties: vec![0; players],
+            shares: vec![0.0; players],
+        }
+    }
+
+    fn add(&mut self, values: &[HandValue]) {
+        self.runs += 1;
+
+        let best = match values.iter().max() {
+            Some(best) => best,
+            None => return,
+        };
+
+        let winners: Vec<usize> = (0..values.len())
+            .filter(|&idx| &values[idx] == best)
+            .collect();
+
+        for &idx in winners.iter() {
+            match winners.len() == 1 {
+                true => self.wins[idx] += 1,
+                false => self.ties[idx] += 1,
+            }
+
+            self.shares[idx] += 1.0 / winners.len() as f64;
+        }
+    }
+
+    pub(crate) fn equities(&self) -> Vec<Equity> {
+        let percent = |count: f64| 100.0 * count / self.runs.max(1) as f64;
+
+        (0..self.wins.len())
+            .map(|idx| Equity {
+                win: percent(self.wins[idx] as f64),
+                tie: percent(self.ties[idx] as f64),
+                equity: percent(self.shares[idx]),
+            })
+            .collect()
+    }
+}
+
+/// Hold'em equity of each player's hole cards, given the board so far
+/// (possibly empty) and cards known to be out of play. Runouts are
+/// enumerated when there are at most [`EXHAUSTIVE_LIMIT`] of them, and
+/// sampled [`DEFAULT_TRIALS`] times from `seed` otherwise.
+pub fn equity(
+    players: &[&str],
+    board: &str,
+    dead: &str,
+    seed: u64,
+) -> Result<Vec<Equity>, EquityError> {
+    equity_with(players, board, dead, seed, RankingRules::High)
+}
+
+/// Same as [`equity`], under the given ranking rules. Short deck deals from
+/// [`Deck::short`] and refuses the deuces through fives.
+pub fn equity_with(
+    players: &[&str],
+    board: &str,
+    dead: &str,
+    seed: u64,
+    rules: RankingRules,
+) -> Result<Vec<Equity>, EquityError> {
+    let deal = Deal::parse(players, board, dead, rules)?;
+
+    let method = match deal.runouts() <= EXHAUSTIVE_LIMIT {
+        true => Method::Exhaustive,
+        false => Method::MonteCarlo {
+            trials: DEFAULT_TRIALS,
+            seed,
+        },
+    };
+
+    Ok(deal.run(method))
+}
+
+/// Same as [`equity`], with the method chosen by the caller.
+pub fn calculate(
+    players: &[&str],
+    board: &str,
+    dead: &str,
+    method: Method,
+) -> Result<Vec<Equity>, EquityError> {
+    calculate_with(players, board, dead, method, RankingRules::High)
+}
+
+/// Same as [`calculate`], under the given ranking rules.
+pub fn calculate_with(
+    players: &[&str],
+    board: &str,
+    dead: &str,
+    method: Method,
+    rules: RankingRules,
+) -> Result<Vec<Equity>, EquityError> {
+    Ok(Deal::parse(players, board, dead, rules)?.run(method))
+}
@@ -0,0 +1,574 @@
+use crate::validate::{self, Duplicate, Holder};
+use crate::{best_five, omaha, parse_cards, Card, HandParseError, HandValue};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/**
+ * Errors
+ */
+/// Why a hand history could not be parsed. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryError {
+    MissingHeader,
+    UnknownLine { line: usize, text: String },
+    InvalidAmount { line: usize, text: String },
+    InvalidCards { line: usize, error: HandParseError },
+    UnknownPlayer { line: usize, player: String },
+}
+
+impl Display for HistoryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MissingHeader => write!(f, "hand history does not start with a hand header"),
+            Self::UnknownLine { line, text } => write!(f, "line {}: unrecognised {:?}", line, text),
+            Self::InvalidAmount { line, text } => {
+                write!(f, "line {}: invalid amount {:?}", line, text)
+            }
+            Self::InvalidCards { line, error } => write!(f, "line {}: {}", line, error),
+            Self::UnknownPlayer { line, player } => {
+                write!(f, "line {}: {:?} is not seated", line, player)
+            }
+        }
+    }
+}
+
+impl Error for HistoryError {}
+
+/**
+ * Record
+ */
+/// Betting round an action belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Round {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+/// What a player did. Amounts are in hundredths of the currency unit, so
+/// `$1.50` is `150`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionKind {
+    Ante(u64),
+    Blind(u64),
+    Fold,
+    Check,
+    Call(u64),
+    Bet(u64),
+    /// Raise by `by`, for a total of `to` on this round.
+    Raise {
+        by: u64,
+        to: u64,
+    },
+    /// Uncalled part of a bet handed back.
+    Returned(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Action {
+    pub round: Round,
+    pub player: String,
+    pub kind: ActionKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seat {
+    pub number: u32,
+    pub player: String,
+    pub stack: u64,
+}
+
+/// A played hand as read from the history text.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HandHistory {
+    pub id: String,
+    pub game: String,
+    pub button: Option<u32>,
+    pub seats: Vec<Seat>,
+    pub actions: Vec<Action>,
+    pub dealt: Vec<(String, Vec<Card>)>,
+    pub board: Vec<Card>,
+    pub shown: Vec<(String, Vec<Card>)>,
+    pub collected: Vec<(String, u64)>,
+    pub total_pot: Option<u64>,
+    pub rake: u64,
+}
+
+/// Disagreement between the history and the replayed hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// Players holding the best hand according to the evaluator did not
+    /// collect anything.
+    Winner {
+        declared: Vec<String>,
+        expected: Vec<String>,
+    },
+    /// Chips put in by the players differ from what was collected and raked.
+    Pot { declared: u64, computed: u64 },
+    /// Cards dealt or shown in more than one place. Hands are numbered by
+    /// their player's index in [`HandHistory::seats`].
+    Duplicates(Vec<Duplicate>),
+}
+
+/// Outcome of replaying a hand history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    /// Chips put in the pot by the players.
+    pub pot: u64,
+    /// Players left holding the best hand, by the crate's evaluator.
+    pub winners: Vec<String>,
+    pub mismatches: Vec<Mismatch>,
+}
+
+#[derive(PartialEq, Eq)]
+enum Section {
+    Seats,
+    Play(Round),
+    Showdown,
+    Summary,
+}
+
+struct Parser<'a> {
+    line: usize,
+    text: &'a str,
+    section: Section,
+    history: HandHistory,
+}
+
+/// Parses a hand history in the common online-room format:
+///
+/// ```text
+/// PokerStars Hand #1: Hold'em No Limit ($0.50/$1.00 USD) - 2023/01/01 12:00:00 ET
+/// Table 'Alpha' 6-max Seat #1 is the button
+/// Seat 1: Alice ($100 in chips)
+/// Alice: posts small blind $0.50
+/// *** HOLE CARDS ***
+/// Dealt to Alice [Ah Kd]
+/// Alice: raises $2 to $3
+/// *** FLOP *** [2c 7d 9h]
+/// ...
+/// ```
+pub fn parse(text: &str) -> Result<HandHistory, HistoryError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    let (_, header) = lines.next().ok_or(HistoryError::MissingHeader)?;
+    let (id, game) = header
+        .split_once("Hand #")
+        .and_then(|(_, rest)| rest.split_once(": "))
+        .ok_or(HistoryError::MissingHeader)?;
+
+    let mut parser = Parser {
+        line: 0,
+        text: "",
+        section: Section::Seats,
+        history: HandHistory {
+            id: id.to_string(),
+            game: game.split(" - ").next().unwrap().to_string(),
+            ..Default::default()
+        },
+    };
+
+    for (line, text) in lines {
+        parser.line = line;
+        parser.text = text;
+        parser.parse_line()?;
+    }
+
+    Ok(parser.history)
+}
+
+impl<'a> Parser<'a> {
+    fn unknown(&self) -> HistoryError {
+        HistoryError::UnknownLine {
+            line: self.line,
+            text: self.text.to_string(),
+        }
+    }
+
+    /// Reads `$1.50`, `€2` or `1500` chips into hundredths.
+    fn amount(&self, text: &str) -> Result<u64, HistoryError> {
+        let invalid = || HistoryError::InvalidAmount {
+            line: self.line,
+            text: text.to_string(),
+        };
+        let digits = text.trim_start_matches(|c: char| !c.is_ascii_digit());
+        let (units, cents) = digits.split_once('.').unwrap_or((digits, "00"));
+
+        let units: u64 = units.parse().map_err(|_| invalid())?;
+        let cents: u64 = match cents.len() {
+            1 => cents.parse::<u64>().map_err(|_| invalid())? * 10,
+            2 => cents.parse().map_err(|_| invalid())?,
+            _ => return Err(invalid()),
+        };
+
+        units
+            .checked_mul(100)
+            .and_then(|units| units.checked_add(cents))
+            .ok_or_else(invalid)
+    }
+
+    /// Cards in every `[...]` group of the line, e.g. `[2c 7d 9h] [Qc]`.
+    fn cards(&self, text: &str) -> Result<Vec<Card>, HistoryError> {
+        let groups: Vec<&str> = text
+            .split('[')
+            .skip(1)
+            .map(|group| group.split(']').next().unwrap())
+            .collect();
+
+        if groups.is_empty() {
+            return Err(self.unknown());
+        }
+
+        parse_cards(&groups.join(" ")).map_err(|error| HistoryError::InvalidCards {
+            line: self.line,
+            error,
+        })
+    }
+
+    fn seated(&self, player: &str) -> Result<String, HistoryError> {
+        match self.history.seats.iter().any(|seat| seat.player == player) {
+            true => Ok(player.to_string()),
+            false => Err(HistoryError::UnknownPlayer {
+                line: self.line,
+                player: player.to_string(),
+            }),
+        }
+    }
+
+    /// Splits `Name: rest` using the seated players' names, which may
+    /// themselves contain colons.
+    fn player_line(&self) -> Option<(String, &'a str)> {
+        let text = self.text;
+
+        self.history
+            .seats
+            .iter()
+            .filter_map(|seat| {
+                text.strip_prefix(seat.player.as_str())
+                    .and_then(|rest| rest.strip_prefix(": "))
+                    .map(|rest| (seat.player.clone(), rest))
+            })
+            .max_by_key(|(player, _)| player.len())
+    }
+
+    fn parse_line(&mut self) -> Result<(), HistoryError> {
+        let text = self.text;
+
+        if let Some(marker) = text.strip_prefix("*** ") {
+            return self.parse_marker(marker);
+        }
+
+        if self.section == Section::Summary {
+            return self.parse_summary();
+        }
+
+        if text.starts_with("Table '") {
+            self.history.button = text
+                .split_once("Seat #")
+                .and_then(|(_, rest)| rest.split(' ').next())
+                .and_then(|number| number.parse().ok());
+            return Ok(());
+        }
+
+        if self.section == Section::Seats {
+            if let Some(rest) = text.strip_prefix("Seat ") {
+                return self.parse_seat(rest);
+            }
+        }
+
+        if let Some(rest) = text.strip_prefix("Dealt to ") {
+            let (player, cards) = rest.split_once(" [").ok_or_else(|| self.unknown())?;
+            let player = self.seated(player)?;
+            let cards = self.cards(&format!("[{}", cards))?;
+            self.history.dealt.push((player, cards));
+            return Ok(());
+        }
+
+        if let Some(rest) = text.strip_prefix("Uncalled bet (") {
+            let (amount, player) = rest
+                .split_once(") returned to ")
+                .ok_or_else(|| self.unknown())?;
+            let kind = ActionKind::Returned(self.amount(amount)?);
+            return self.push_action(player, kind);
+        }
+
+        if let Some((player, rest)) = text.split_once(" collected ") {
+            let player = self.seated(player)?;
+            let amount = rest.split(' ').next().unwrap();
+            let amount = self.amount(amount)?;
+            self.history.collected.push((player, amount));
+            return Ok(());
+        }
+
+        let (player, action) = self.player_line().ok_or_else(|| self.unknown())?;
+        self.parse_action(player, action)
+    }
+
+    fn parse_marker(&mut self, marker: &str) -> Result<(), HistoryError> {
+        let (name, cards) = marker.split_once(" ***").ok_or_else(|| self.unknown())?;
+
+        self.section = match name {
+            "HOLE CARDS" => Section::Play(Round::Preflop),
+            "FLOP" => Section::Play(Round::Flop),
+            "TURN" => Section::Play(Round::Turn),
+            "RIVER" => Section::Play(Round::River),
+            "SHOW DOWN" => Section::Showdown,
+            "SUMMARY" => Section::Summary,
+            _ => return Err(self.unknown()),
+        };
+
+        if let Section::Play(Round::Flop | Round::Turn | Round::River) = self.section {
+            self.history.board = self.cards(cards)?;
+        }
+
+        Ok(())
+    }
+
+    fn parse_seat(&mut self, rest: &str) -> Result<(), HistoryError> {
+        let (number, rest) = rest.split_once(": ").ok_or_else(|| self.unknown())?;
+        let (player, stack) = rest.rsplit_once(" (").ok_or_else(|| self.unknown())?;
+        let stack = stack
+            .strip_suffix(" in chips)")
+            .ok_or_else(|| self.unknown())?;
+
+        self.history.seats.push(Seat {
+            number: number.parse().map_err(|_| self.unknown())?,
+            player: player.to_string(),
+            stack: self.amount(stack)?,
+        });
+
+        Ok(())
+    }
+
+    fn parse_summary(&mut self) -> Result<(), HistoryError> {
+        let text = self.text;
+
+        if let Some(rest) = text.strip_prefix("Total pot ") {
+            let mut parts = rest.split(" | ");
+            self.history.total_pot = Some(self.amount(parts.next().unwrap())?);
+
+            for part in parts {
+                if let Some(rake) = part.strip_prefix("Rake ") {
+                    self.history.rake = self.amount(rake)?;
+                }
+            }
+        } else if text.starts_with("Board [") {
+            self.history.board = self.cards(text)?;
+        }
+
+        // Per-seat summary lines repeat what the body already said.
+        Ok(())
+    }
+
+    fn parse_action(&mut self, player: String, action: &str) -> Result<(), HistoryError> {
+        let action = action.trim_end_matches(" and is all-in");
+        let words: Vec<&str> = action.split(' ').collect();
+
+        let kind = match words[..] {
+            ["posts", "the", "ante", amount] => ActionKind::Ante(self.amount(amount)?),
+            ["posts", "small", "blind", amount]
+            | ["posts", "big", "blind", amount]
+            | ["posts", "small", "&", "big", "blinds", amount] => {
+                ActionKind::Blind(self.amount(amount)?)
+            }
+            ["folds", ..] => ActionKind::Fold,
+            ["checks"] => ActionKind::Check,
+            ["calls", amount] => ActionKind::Call(self.amount(amount)?),
+            ["bets", amount] => ActionKind::Bet(self.amount(amount)?),
+            ["raises", by, "to", to] => ActionKind::Raise {
+                by: self.amount(by)?,
+                to: self.amount(to)?,
+            },
+            ["shows", ..] => {
+                let cards = self.cards(action)?;
+                self.history.shown.push((player, cards));
+                return Ok(());
+            }
+            ["mucks", "hand"] | ["doesn't", "show", "hand"] => return Ok(()),
+            _ => return Err(self.unknown()),
+        };
+
+        self.push_action(&player, kind)
+    }
+
+    fn push_action(&mut self, player: &str, kind: ActionKind) -> Result<(), HistoryError> {
+        let round = match self.section {
+            Section::Play(round) => round,
+            _ => Round::Preflop,
+        };
+
+        self.history.actions.push(Action {
+            round,
+            player: self.seated(player)?,
+            kind,
+        });
+
+        Ok(())
+    }
+}
+
+impl HandHistory {
+    /// Chips each player put in the pot, net of uncalled bets.
+    pub fn contributions(&self) -> HashMap<String, u64> {
+        let mut total: HashMap<String, u64> = HashMap::new();
+        let mut round_in: HashMap<(Round, &str), u64> = HashMap::new();
+
+        for action in self.actions.iter() {
+            let key = (action.round, action.player.as_str());
+            let added = match action.kind {
+                ActionKind::Ante(amount) => amount,
+                ActionKind::Blind(amount) | ActionKind::Call(amount) | ActionKind::Bet(amount) => {
+                    *round_in.entry(key).or_insert(0) += amount;
+                    amount
+                }
+                ActionKind::Raise { to, .. } => {
+                    let already = round_in.insert(key, to).unwrap_or(0);
+                    to.saturating_sub(already)
+                }
+                ActionKind::Returned(amount) => {
+                    let entry = total.entry(action.player.clone()).or_insert(0);
+                    *entry = entry.saturating_sub(amount);
+                    continue;
+                }
+                ActionKind::Fold | ActionKind::Check => continue,
+            };
+
+            *total.entry(action.player.clone()).or_insert(0) += added;
+        }
+
+        total
+    }
+
+    fn folded(&self, player: &str) -> bool {
+        self.actions
+            .iter()
+            .any(|action| action.player == player && action.kind == ActionKind::Fold)
+    }
+
+    fn hand_value(&self, hole: &[Card]) -> Option<HandValue> {
+        if hole.len() + self.board.len() < 5 {
+            return None;
+        }
+
+        if self.game.contains("Omaha") {
+            return omaha::best_high(hole, &self.board).map(|best| best.value);
+        }
+
+        let mut cards = hole.to_vec();
+        cards.extend(&self.board);
+        Some(best_five(&cards).value)
+    }
+
+    /// Every card seen more than once between the players' hole cards,
+    /// dealt or shown, and the board.
+    fn duplicates(&self) -> Vec<Duplicate> {
+        let mut sources: Vec<(Holder, Vec<Card>)> = vec![];
+
+        for (idx, seat) in self.seats.iter().enumerate() {
+            let mut hole: Vec<Card> = vec![];
+            let held = self
+                .dealt
+                .iter()
+                .chain(&self.shown)
+                .filter(|(player, _)| *player == seat.player)
+                .flat_map(|(_, cards)| cards);
+
+            for &card in held {
+                if !hole.contains(&card) {
+                    hole.push(card);
+                }
+            }
+
+            sources.push((Holder::Hand(idx), hole));
+        }
+
+        sources.push((Holder::Board, self.board.clone()));
+        validate::duplicates(sources)
+    }
+
+    /// Players still holding the best hand: the best shown hands, or the
+    /// last player standing when everybody else folded.
+    fn expected_winners(&self) -> Vec<String> {
+        let values: Vec<(&String, HandValue)> = self
+            .shown
+            .iter()
+            .filter(|(player, _)| !self.folded(player))
+            .filter_map(|(player, hole)| self.hand_value(hole).map(|value| (player, value)))
+            .collect();
+
+        match values.iter().map(|(_, value)| value).max() {
+            Some(best) => values
+                .iter()
+                .filter(|(_, value)| value == best)
+                .map(|(player, _)| player.to_string())
+                .collect(),
+            None => {
+                let standing: Vec<String> = self
+                    .seats
+                    .iter()
+                    .filter(|seat| {
+                        self.actions
+                            .iter()
+                            .any(|action| action.player == seat.player)
+                    })
+                    .filter(|seat| !self.folded(&seat.player))
+                    .map(|seat| seat.player.clone())
+                    .collect();
+
+                match standing.len() {
+                    1 => standing,
+                    _ => vec![],
+                }
+            }
+        }
+    }
+
+    /// Replays the hand, checking the declared winners and pot against the
+    /// crate's own evaluator and the betting actions.
+    pub fn replay(&self) -> Replay {
+        let pot: u64 = self.contributions().values().sum();
+        let winners = self.expected_winners();
+        let mut mismatches = vec![];
+        let duplicates = self.duplicates();
+
+        if !duplicates.is_empty() {
+            mismatches.push(Mismatch::Duplicates(duplicates));
+        }
+
+        let mut declared: Vec<String> = self
+            .collected
+            .iter()
+            .map(|(player, _)| player.clone())
+            .collect();
+        declared.sort();
+        declared.dedup();
+
+        if winners.iter().any(|winner| !declared.contains(winner)) {
+            mismatches.push(Mismatch::Winner {
+                declared,
+                expected: winners.clone(),
+            });
+        }
+
+        let paid = self.collected.iter().map(|(_, amount)| amount).sum::<u64>() + self.rake;
+
+        if paid != pot {
+            mismatches.push(Mismatch::Pot {
+                declared: paid,
+                computed: pot,
+            });
+        }
+
+        Replay {
+            pot,
+            winners,
+            mismatches,
+        }
+    }
+}
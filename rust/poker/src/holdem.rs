@@ -0,0 +1,91 @@
+use crate::{best_five_with, parse_cards, BestHand, Card, HandParseError, RankingRules};
+
+const HOLE_CARDS: usize = 2;
+const BOARD_CARDS: usize = 5;
+const FLOP_CARDS: usize = 3;
+
+/// Parses hole cards, which must number one of `sizes`.
+pub(crate) fn parse_hole(hole: &str, sizes: &[usize]) -> Result<Vec<Card>, HandParseError> {
+    let cards = parse_cards(hole)?;
+
+    if !sizes.contains(&cards.len()) {
+        return Err(HandParseError::WrongCardCount {
+            hand: hole.to_string(),
+            count: cards.len(),
+            expected: sizes[0],
+        });
+    }
+
+    Ok(cards)
+}
+
+/// Parses a board of three to five cards, none of which may also be in `hole`.
+pub(crate) fn parse_board(board: &str, hole: &[Card]) -> Result<Vec<Card>, HandParseError> {
+    let cards = parse_cards(board)?;
+
+    if !(FLOP_CARDS..=BOARD_CARDS).contains(&cards.len()) {
+        return Err(HandParseError::WrongCardCount {
+            hand: board.to_string(),
+            count: cards.len(),
+            expected: BOARD_CARDS,
+        });
+    }
+
+    match cards.iter().position(|card| hole.contains(card)) {
+        Some(position) => Err(HandParseError::DuplicateCard {
+            token: board.split_whitespace().nth(position).unwrap().to_string(),
+            position,
+        }),
+        None => Ok(cards),
+    }
+}
+
+/// Best five cards out of two hole cards, such as `"AS KD"`, and the board.
+pub fn best_hand(hole: &str, board: &str) -> Result<BestHand, HandParseError> {
+    best_hand_with(hole, board, RankingRules::High)
+}
+
+/// Same as [`best_hand`], under the given ranking rules. Cards missing from
+/// their deck, such as a deuce in short deck, are refused.
+pub fn best_hand_with(
+    hole: &str,
+    board: &str,
+    rules: RankingRules,
+) -> Result<BestHand, HandParseError> {
+    let mut cards = parse_hole(hole, &[HOLE_CARDS])?;
+    rules.check(hole, &cards)?;
+
+    let board_cards = parse_board(board, &cards)?;
+    rules.check(board, &board_cards)?;
+    cards.extend(board_cards);
+
+    Ok(best_five_with(&cards, rules))
+}
+
+/// Returns the hole cards of every player sharing the best hand on `board`.
+pub fn showdown<'a>(players: &[&'a str], board: &str) -> Result<Vec<&'a str>, HandParseError> {
+    showdown_with(players, board, RankingRules::High)
+}
+
+/// Same as [`showdown`], under the given ranking rules.
+pub fn showdown_with<'a>(
+    players: &[&'a str],
+    board: &str,
+    rules: RankingRules,
+) -> Result<Vec<&'a str>, HandParseError> {
+    let hands: Vec<(&'a str, BestHand)> = players
+        .iter()
+        .map(|&hole| best_hand_with(hole, board, rules).map(|best| (hole, best)))
+        .collect::<Result<_, _>>()?;
+
+    let highest = match hands.iter().map(|(_, best)| best.value()).max() {
+        Some(value) => value,
+        None => return Ok(vec![]),
+    };
+
+    Ok(hands
+        .iter()
+        .filter(|(_, best)| best.value() == highest)
+        .map(|&(hole, _)| hole)
+        .collect())
+}
@@ -2,66 +2,234 @@
 // ///
 // /// Note the type signature: this function should return _the same_ reference to
 // /// the winning hand(s) as were passed in, not reconstructed strings which happen to be equal.
-use std::cmp::Reverse;
+use std::cmp::{Ordering, Reverse};
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+pub mod deck;
+pub mod equity;
+pub mod history;
+pub mod holdem;
+pub mod lookup;
+pub mod omaha;
+pub mod range;
+pub mod showdown;
+pub mod validate;
+
+const HAND_SIZE: usize = 5;
+/// Ace-low straights, sorted high to low: `5-4-3-2-A`, and `9-8-7-6-A` once
+/// the deuces through fives are stripped for short deck.
+const WHEEL: [u8; 5] = [14, 5, 4, 3, 2];
+const SHORT_WHEEL: [u8; 5] = [14, 9, 8, 7, 6];
+
+/**
+ * Errors
+ */
+/// Why a hand could not be parsed. Positions are the zero-based index of the
+/// offending card within the hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandParseError {
+    InvalidRank {
+        token: String,
+        position: usize,
+    },
+    InvalidSuit {
+        token: String,
+        position: usize,
+    },
+    WrongCardCount {
+        hand: String,
+        count: usize,
+        expected: usize,
+    },
+    DuplicateCard {
+        token: String,
+        position: usize,
+    },
+}
+
+impl Display for HandParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::InvalidRank { token, position } => {
+                write!(
+                    f,
+                    "invalid rank in card {:?} at position {}",
+                    token, position
+                )
+            }
+            Self::InvalidSuit { token, position } => {
+                write!(
+                    f,
+                    "invalid suit in card {:?} at position {}",
+                    token, position
+                )
+            }
+            Self::WrongCardCount {
+                hand,
+                count,
+                expected,
+            } => write!(
+                f,
+                "hand {:?} has {} cards, expected {}",
+                hand, count, expected
+            ),
+            Self::DuplicateCard { token, position } => {
+                write!(f, "duplicate card {:?} at position {}", token, position)
+            }
+        }
+    }
+}
+
+impl Error for HandParseError {}
 
 /**
  * Card
  */
-#[derive(PartialEq, Eq)]
-enum CardKind {
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum CardKind {
     Club,
     Diamond,
     Heart,
     Spade,
-    NotFound,
+    /// Always wild; a joker has no suit and a value of 0.
+    Joker,
 }
 
-struct Card {
+const SUITS: [CardKind; 4] = [
+    CardKind::Club,
+    CardKind::Diamond,
+    CardKind::Heart,
+    CardKind::Spade,
+];
+const JOKER: &str = "JK";
+const JOKER_GLYPH: &str = "🃏";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Card {
     kind: CardKind,
     value: u8,
 }
 
 impl Card {
-    fn from(card: &str) -> Self {
-        let length = card.len();
-        let kind = Self::kind(card.get(length - 1..));
+    /// Reads `10h`, `Th`, `TH` or `T♥` alike; a joker is `JK` or `🃏`.
+    fn parse(card: &str, position: usize) -> Result<Self, HandParseError> {
+        let token = card.to_string();
+        let card = card.to_uppercase();
+
+        if card == JOKER || card == JOKER_GLYPH {
+            return Ok(Self::joker());
+        }
 
-        let str_val = card.get(..length - 1).unwrap();
-        let value: u8 = Self::value(str_val);
+        let length = card.char_indices().last().map_or(0, |(idx, _)| idx);
 
-        Self { kind, value }
+        match (
+            Self::parse_kind(&card[length..]),
+            Self::parse_value(&card[..length]),
+        ) {
+            (None, _) => Err(HandParseError::InvalidSuit { token, position }),
+            (_, None) => Err(HandParseError::InvalidRank { token, position }),
+            (Some(kind), Some(value)) => Ok(Self { kind, value }),
+        }
     }
 
-    fn kind(k: Option<&str>) -> CardKind {
+    fn parse_kind(k: &str) -> Option<CardKind> {
         match k {
-            Some("C") => CardKind::Club,
-            Some("D") => CardKind::Diamond,
-            Some("H") => CardKind::Heart,
-            Some("S") => CardKind::Spade,
-            _ => CardKind::NotFound,
+            "C" | "♣" | "♧" => Some(CardKind::Club),
+            "D" | "♦" | "♢" => Some(CardKind::Diamond),
+            "H" | "♥" | "♡" => Some(CardKind::Heart),
+            "S" | "♠" | "♤" => Some(CardKind::Spade),
+            _ => None,
         }
     }
 
-    fn value(val: &str) -> u8 {
-        match val.parse() {
-            Ok(v) => v,
-            _ => match val {
-                "J" => 11,
-                "Q" => 12,
-                "K" => 13,
-                "A" => 14,
-                _ => 0,
-            },
+    fn parse_value(val: &str) -> Option<u8> {
+        match val {
+            "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" | "10" => val.parse().ok(),
+            "T" => Some(10),
+            "J" => Some(11),
+            "Q" => Some(12),
+            "K" => Some(13),
+            "A" => Some(14),
+            _ => None,
         }
     }
+
+    pub fn joker() -> Self {
+        Self {
+            kind: CardKind::Joker,
+            value: 0,
+        }
+    }
+
+    pub fn is_joker(&self) -> bool {
+        self.kind == CardKind::Joker
+    }
+
+    pub fn kind(&self) -> CardKind {
+        self.kind
+    }
+
+    /// Rank of the card, from 2 up to 14 for the ace.
+    pub fn value(&self) -> u8 {
+        self.value
+    }
+}
+
+/// Writes the card compactly, `Ah` or `Td`, or with a suit glyph, `A♥` or
+/// `T♦`, with the alternate flag (`{:#}`). Both read back with [`parse_cards`].
+impl Display for Card {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let suit = match (self.kind, f.alternate()) {
+            (CardKind::Club, false) => "c",
+            (CardKind::Diamond, false) => "d",
+            (CardKind::Heart, false) => "h",
+            (CardKind::Spade, false) => "s",
+            (CardKind::Club, true) => "♣",
+            (CardKind::Diamond, true) => "♦",
+            (CardKind::Heart, true) => "♥",
+            (CardKind::Spade, true) => "♠",
+            (CardKind::Joker, false) => return write!(f, "{}", JOKER),
+            (CardKind::Joker, true) => return write!(f, "{}", JOKER_GLYPH),
+        };
+
+        let value = match self.value {
+            10 => 'T',
+            11 => 'J',
+            12 => 'Q',
+            13 => 'K',
+            14 => 'A',
+            value => (b'0' + value) as char,
+        };
+
+        write!(f, "{}{}", value, suit)
+    }
+}
+
+/// Writes space-separated cards, passing the alternate flag on to each card.
+fn write_cards(f: &mut Formatter<'_>, cards: &[Card]) -> FmtResult {
+    for (idx, card) in cards.iter().enumerate() {
+        if idx > 0 {
+            write!(f, " ")?;
+        }
+
+        match f.alternate() {
+            true => write!(f, "{:#}", card)?,
+            false => write!(f, "{}", card)?,
+        }
+    }
+
+    Ok(())
 }
 
 /**
  * Hand
  */
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy)]
-enum HandRank {
+/// Category of a five-card hand, from the weakest to the strongest.
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Hash, Default)]
+pub enum HandRank {
+    #[default]
     HighCard,
     OnePair,
     TwoPair,
@@ -71,262 +239,627 @@ enum HandRank {
     FullHouse,
     FourOfKind,
     StraightFlush,
+    /// Only possible with wild cards.
+    FiveOfKind,
 }
 
-impl Default for HandRank {
-    fn default() -> Self {
-        HandRank::HighCard
+impl Display for HandRank {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let name = match self {
+            Self::HighCard => "High card",
+            Self::OnePair => "One pair",
+            Self::TwoPair => "Two pair",
+            Self::ThreeOfKind => "Three of a kind",
+            Self::Straight => "Straight",
+            Self::Flush => "Flush",
+            Self::FullHouse => "Full house",
+            Self::FourOfKind => "Four of a kind",
+            Self::StraightFlush => "Straight flush",
+            Self::FiveOfKind => "Five of a kind",
+        };
+
+        write!(f, "{}", name)
     }
 }
 
-#[derive(Default)]
-struct Rank {
-    main: HandRank,
-    major: u8,
-    minor: u8,
-    left: Vec<u8>,
+/// How hands are ranked against each other.
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Hash, Default)]
+pub enum RankingRules {
+    /// Standard high hands; the ace may start a five-high straight.
+    #[default]
+    High,
+    /// Lowball as in Razz: straights and flushes are ignored, the ace is
+    /// always low and `5-4-3-2-A` is the best hand.
+    AceToFive,
+    /// Kansas City lowball: hands are ranked as high hands with the ace
+    /// always high, and the weakest one wins. `7-5-4-3-2` is the best hand.
+    DeuceToSeven,
+    /// Short deck (6+) high hands: a flush beats a full house, and the ace
+    /// may start the nine-high straight `9-8-7-6-A`.
+    ShortDeck,
 }
 
-#[derive(Default)]
-struct Hand {
-    idx: usize,
-    rank: Rank,
-}
-
-impl Hand {
-    fn from(hand: &str, idx: usize) -> Self {
-        let str_cards = hand.split(' ').collect::<Vec<&str>>();
-        let mut cards: Vec<Card> = vec![];
-
-        for card in str_cards {
-            cards.push(Card::from(card));
+impl RankingRules {
+    /// Lowest rank in the deck the rules are played with.
+    pub(crate) fn lowest_rank(self) -> u8 {
+        match self {
+            Self::ShortDeck => 6,
+            _ => 2,
         }
+    }
 
-        if cards.iter().max_by_key(|c| c.value).unwrap().value == 14
-            && cards.iter().any(|c| c.value == 2)
-            && cards.iter().any(|c| c.value == 3)
-            && cards.iter().any(|c| c.value == 4)
-            && cards.iter().any(|c| c.value == 5)
+    /// Refuses cards missing from the rules' deck, such as a five in short
+    /// deck, as [`HandParseError::InvalidRank`].
+    pub(crate) fn check(self, text: &str, cards: &[Card]) -> Result<(), HandParseError> {
+        match cards
+            .iter()
+            .position(|card| !card.is_joker() && card.value < self.lowest_rank())
         {
-            let ace = cards.iter().position(|c| c.value == 14).unwrap();
-            cards.get_mut(ace).unwrap().value = 1;
+            Some(position) => Err(HandParseError::InvalidRank {
+                token: text.split_whitespace().nth(position).unwrap().to_string(),
+                position,
+            }),
+            None => Ok(()),
         }
+    }
+}
 
-        cards.sort_by_key(|c| Reverse(c.value));
+/// Strength of a hand: its category followed by the ranks that break ties
+/// within it, most significant first. Better hands compare greater under
+/// the rules they were ranked with, so lowball hands order in reverse.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct HandValue {
+    rules: RankingRules,
+    category: HandRank,
+    ranks: Vec<u8>,
+}
+
+impl Ord for HandValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let strength =
+            (self.ordered_category(), &self.ranks).cmp(&(other.ordered_category(), &other.ranks));
 
-        let rank = Self::hand_rank(&cards);
+        self.rules.cmp(&other.rules).then(match self.is_low() {
+            true => strength.reverse(),
+            false => strength,
+        })
+    }
+}
 
-        Self { idx, rank }
+impl PartialOrd for HandValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
+}
 
-    fn hand_rank(cards: &Vec<Card>) -> Rank {
-        if cards.is_empty() {
-            return Rank::default();
+impl HandValue {
+    fn new(category: HandRank, ranks: Vec<u8>) -> Self {
+        Self {
+            rules: RankingRules::High,
+            category,
+            ranks,
         }
+    }
 
-        let first = cards.first().unwrap();
-        let mut is_straight_flush = true;
-        let mut is_flush = true;
-        let mut is_straight = true;
+    fn is_low(&self) -> bool {
+        matches!(
+            self.rules,
+            RankingRules::AceToFive | RankingRules::DeuceToSeven
+        )
+    }
 
-        if first.value >= 5 {
-            let mut counter = 1;
+    /// Category in the order the rules rank them.
+    fn ordered_category(&self) -> HandRank {
+        match (self.rules, self.category) {
+            (RankingRules::ShortDeck, HandRank::Flush) => HandRank::FullHouse,
+            (RankingRules::ShortDeck, HandRank::FullHouse) => HandRank::Flush,
+            (_, category) => category,
+        }
+    }
 
-            for card in cards.get(1..).unwrap() {
-                if card.value != first.value - counter {
-                    is_straight_flush = false;
-                    is_straight = false;
-                }
+    fn from_cards(cards: &[Card]) -> Self {
+        Self::with_rules(cards, RankingRules::High)
+    }
 
-                if card.kind != first.kind {
-                    is_straight_flush = false;
-                    is_flush = false;
-                }
+    pub(crate) fn with_rules(cards: &[Card], rules: RankingRules) -> Self {
+        Self::with_wild(cards, rules, &[])
+    }
 
-                counter += 1;
-            }
+    /// Ranks the best hand the wild cards can make: jokers, plus any card
+    /// whose value is listed in `wild`.
+    pub(crate) fn with_wild(cards: &[Card], rules: RankingRules, wild: &[u8]) -> Self {
+        let (wilds, naturals): (Vec<Card>, Vec<Card>) = cards
+            .iter()
+            .partition(|card| card.is_joker() || wild.contains(&card.value));
+
+        if wilds.is_empty() {
+            return Self::natural(cards, rules);
         }
 
-        if is_straight_flush {
-            return Rank {
-                main: HandRank::StraightFlush,
-                major: cards.iter().max_by_key(|card| card.value).unwrap().value,
-                ..Default::default()
-            };
+        // Suits only matter for flushes, so the wild cards either all follow
+        // the suit of the natural cards, or break the flush.
+        let base = naturals.first().map_or(0, |card| {
+            SUITS.iter().position(|&kind| kind == card.kind).unwrap()
+        });
+        let suited = naturals.iter().all(|card| card.kind == SUITS[base]);
+
+        let mut suits: Vec<Vec<CardKind>> = vec![(0..wilds.len())
+            .map(|idx| SUITS[(base + 1 + idx) % SUITS.len()])
+            .collect()];
+
+        if suited {
+            suits.push(vec![SUITS[base]; wilds.len()]);
         }
 
-        let mut hm: HashMap<u8, u8> = HashMap::new();
+        Self::rank_multisets(wilds.len(), rules.lowest_rank())
+            .iter()
+            .flat_map(|values| {
+                suits.iter().map(|kinds| {
+                    let mut hand = naturals.clone();
+                    hand.extend(
+                        values
+                            .iter()
+                            .zip(kinds)
+                            .map(|(&value, &kind)| Card { kind, value }),
+                    );
+
+                    Self::natural(&hand, rules)
+                })
+            })
+            .max()
+            .unwrap()
+    }
 
-        for card in cards.iter() {
-            *hm.entry(card.value).or_insert(0) += 1;
+    /// Every choice of `k` ranks from `from` up to the ace, repeats allowed.
+    fn rank_multisets(k: usize, from: u8) -> Vec<Vec<u8>> {
+        if k == 0 {
+            return vec![vec![]];
         }
 
-        let keys_count = hm.len();
+        (from..=14)
+            .flat_map(|value| {
+                Self::rank_multisets(k - 1, value)
+                    .into_iter()
+                    .map(move |mut rest| {
+                        rest.insert(0, value);
+                        rest
+                    })
+            })
+            .collect()
+    }
 
-        if hm.values().any(|&v| v == 4) {
-            return Rank {
-                main: HandRank::FourOfKind,
-                major: *hm.iter().max_by_key(|(_, &v)| v).unwrap().0,
-                minor: *hm.iter().min_by_key(|(_, &v)| v).unwrap().0,
-                ..Default::default()
-            };
+    fn natural(cards: &[Card], rules: RankingRules) -> Self {
+        match rules {
+            RankingRules::High => Self::high(cards, Some(WHEEL)),
+            RankingRules::AceToFive => Self::ace_to_five(cards),
+            RankingRules::DeuceToSeven => Self {
+                rules,
+                ..Self::high(cards, None)
+            },
+            RankingRules::ShortDeck => Self {
+                rules,
+                ..Self::high(cards, Some(SHORT_WHEEL))
+            },
         }
+    }
 
-        if keys_count == 2 && hm.values().any(|&v| v == 3) {
-            return Rank {
-                main: HandRank::FullHouse,
-                major: *hm.iter().max_by_key(|(_, &v)| v).unwrap().0,
-                minor: *hm.iter().min_by_key(|(_, &v)| v).unwrap().0,
-                ..Default::default()
-            };
+    /// Counts of each rank and the ranks themselves, largest groups first and
+    /// then by rank: the tiebreak order for every category.
+    fn groups(values: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let mut hm: HashMap<u8, u8> = HashMap::new();
+
+        for &value in values.iter() {
+            *hm.entry(value).or_insert(0) += 1;
         }
 
-        if is_flush {
-            return Rank {
-                main: HandRank::Flush,
-                major: cards[0].value,
-                minor: cards[1].value,
-                left: cards[2..].iter().map(|card| card.value).collect(),
-            };
+        let mut groups: Vec<(u8, u8)> = hm.into_iter().map(|(k, v)| (v, k)).collect();
+        groups.sort_by_key(|&group| Reverse(group));
+
+        groups.into_iter().unzip()
+    }
+
+    /// High hand, with the ace playing low in the `wheel` straight if any.
+    fn high(cards: &[Card], wheel: Option<[u8; 5]>) -> Self {
+        let mut values: Vec<u8> = cards.iter().map(|card| card.value).collect();
+        values.sort_by_key(|&value| Reverse(value));
+
+        if let Some(wheel) = wheel.filter(|wheel| values == wheel) {
+            values = wheel[1..].to_vec();
+            values.push(wheel[4] - 1);
         }
 
-        if is_straight {
-            return Rank {
-                main: HandRank::Straight,
-                major: cards[0].value,
-                ..Default::default()
-            };
+        let is_flush = cards.iter().all(|card| card.kind == cards[0].kind);
+        let is_straight = values.windows(2).all(|pair| pair[0] == pair[1] + 1);
+
+        if is_straight && is_flush {
+            return Self::new(HandRank::StraightFlush, vec![values[0]]);
         }
 
-        if hm.values().any(|&v| v == 3) {
-            let major = *hm.iter().max_by_key(|(_, &v)| v).unwrap().0;
-            let minor = *hm.keys().filter(|&&key| key != major).max().unwrap();
-
-            return Rank {
-                main: HandRank::ThreeOfKind,
-                major,
-                minor,
-                left: cards
-                    .iter()
-                    .filter(|card| ![major, minor].contains(&card.value))
-                    .map(|card| card.value)
-                    .collect(),
-            };
+        let (counts, ranks) = Self::groups(&values);
+
+        match counts[..] {
+            [5] => Self::new(HandRank::FiveOfKind, ranks),
+            [4, 1] => Self::new(HandRank::FourOfKind, ranks),
+            [3, 2] => Self::new(HandRank::FullHouse, ranks),
+            _ if is_flush => Self::new(HandRank::Flush, values),
+            _ if is_straight => Self::new(HandRank::Straight, vec![values[0]]),
+            [3, 1, 1] => Self::new(HandRank::ThreeOfKind, ranks),
+            [2, 2, 1] => Self::new(HandRank::TwoPair, ranks),
+            [2, 1, 1, 1] => Self::new(HandRank::OnePair, ranks),
+            _ => Self::new(HandRank::HighCard, values),
         }
+    }
 
-        if keys_count == 3 && hm.values().any(|&v| v == 2) {
-            let mut pairs: Vec<u8> = hm
-                .iter()
-                .filter(|(_, &v)| v == 2)
-                .map(|(&k, _)| k)
-                .collect();
-
-            pairs.sort();
-
-            return Rank {
-                main: HandRank::TwoPair,
-                major: pairs[1],
-                minor: pairs[0],
-                left: cards
-                    .iter()
-                    .filter(|card| ![pairs[1], pairs[0]].contains(&card.value))
-                    .map(|card| card.value)
-                    .collect(),
-            };
+    fn ace_to_five(cards: &[Card]) -> Self {
+        let values: Vec<u8> = cards
+            .iter()
+            .map(|card| match card.value {
+                14 => 1,
+                value => value,
+            })
+            .collect();
+
+        let (counts, ranks) = Self::groups(&values);
+
+        let category = match counts[..] {
+            [5] => HandRank::FiveOfKind,
+            [4, 1] => HandRank::FourOfKind,
+            [3, 2] => HandRank::FullHouse,
+            [3, 1, 1] => HandRank::ThreeOfKind,
+            [2, 2, 1] => HandRank::TwoPair,
+            [2, 1, 1, 1] => HandRank::OnePair,
+            _ => HandRank::HighCard,
+        };
+
+        Self {
+            rules: RankingRules::AceToFive,
+            category,
+            ranks,
         }
+    }
+
+    pub fn rules(&self) -> RankingRules {
+        self.rules
+    }
+
+    pub fn category(&self) -> HandRank {
+        self.category
+    }
+
+    /// Ranks compared after the category, e.g. `[13, 4]` for kings full of fours.
+    /// A five-high straight is reported as `[5]`, as is an ace-to-five ace.
+    pub fn ranks(&self) -> &[u8] {
+        &self.ranks
+    }
 
-        if hm.values().any(|&v| v == 2) {
-            let major = *hm.iter().max_by_key(|(_, v)| **v).unwrap().0;
-            let minor = *hm.keys().filter(|&&key| key != major).max().unwrap();
-
-            return Rank {
-                main: HandRank::OnePair,
-                major,
-                minor,
-                left: cards
-                    .iter()
-                    .filter(|card| ![major, minor].contains(&card.value))
-                    .map(|card| card.value)
-                    .collect(),
-            };
+    /// Trailing ranks left out of [`HandValue::description`], which only
+    /// matter between hands that read the same, e.g. `[9, 4]` for a pair of
+    /// kings with a nine and a four.
+    pub fn kickers(&self) -> &[u8] {
+        let named = match self.category {
+            HandRank::HighCard if self.is_low() => 2,
+            HandRank::TwoPair | HandRank::FullHouse => 2,
+            _ => 1,
+        };
+
+        match self.category {
+            HandRank::FullHouse
+            | HandRank::Straight
+            | HandRank::StraightFlush
+            | HandRank::FiveOfKind => &[],
+            _ => &self.ranks[named.min(self.ranks.len())..],
         }
+    }
 
-        Rank {
-            main: HandRank::HighCard,
-            major: cards[0].value,
-            minor: cards[1].value,
-            left: cards[2..].iter().map(|card| card.value).collect(),
+    /// Human-readable summary such as `"Full house, Kings over Fours"`.
+    pub fn description(&self) -> String {
+        let ranks = &self.ranks;
+
+        match self.category {
+            HandRank::HighCard if self.is_low() => format!(
+                "{}-{} low",
+                rank_name(ranks[0]),
+                rank_name(ranks[1]).to_lowercase()
+            ),
+            HandRank::HighCard => format!("{}, {}", self.category, rank_name(ranks[0])),
+            HandRank::OnePair
+            | HandRank::ThreeOfKind
+            | HandRank::FourOfKind
+            | HandRank::FiveOfKind => {
+                format!("{}, {}", self.category, rank_plural(ranks[0]))
+            }
+            HandRank::TwoPair => format!(
+                "{}, {} and {}",
+                self.category,
+                rank_plural(ranks[0]),
+                rank_plural(ranks[1])
+            ),
+            HandRank::FullHouse => format!(
+                "{}, {} over {}",
+                self.category,
+                rank_plural(ranks[0]),
+                rank_plural(ranks[1])
+            ),
+            HandRank::StraightFlush if ranks[0] == 14 => "Royal flush".to_string(),
+            HandRank::Straight | HandRank::Flush | HandRank::StraightFlush => {
+                format!("{}, {} high", self.category, rank_name(ranks[0]))
+            }
         }
     }
 }
 
-/// Main
-pub fn winning_hands<'a>(hands: &[&'a str]) -> Vec<&'a str> {
-    if hands.len() <= 1 {
-        return hands.into();
+impl Display for HandValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.description())
     }
+}
 
-    let poker_hands: Vec<Hand> = hands
-        .iter()
-        .enumerate()
-        .map(|(idx, &hand)| Hand::from(hand, idx))
-        .collect();
+/// Name of a rank as used in descriptions, such as `"Ten"`. Anything that
+/// is not a deuce through a king is an ace.
+pub fn rank_name(value: u8) -> &'static str {
+    match value {
+        2 => "Two",
+        3 => "Three",
+        4 => "Four",
+        5 => "Five",
+        6 => "Six",
+        7 => "Seven",
+        8 => "Eight",
+        9 => "Nine",
+        10 => "Ten",
+        11 => "Jack",
+        12 => "Queen",
+        13 => "King",
+        _ => "Ace",
+    }
+}
 
-    let highest = poker_hands
-        .iter()
-        .max_by_key(|hand| hand.rank.main)
+fn rank_plural(value: u8) -> String {
+    match value {
+        6 => "Sixes".to_string(),
+        _ => format!("{}s", rank_name(value)),
+    }
+}
+
+/// A parsed five-card hand.
+#[derive(Debug, Clone)]
+pub struct Hand {
+    cards: Vec<Card>,
+    value: HandValue,
+}
+
+impl Hand {
+    fn from(cards: Vec<Card>) -> Self {
+        let value = HandValue::from_cards(&cards);
+
+        Self { cards, value }
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn value(&self) -> &HandValue {
+        &self.value
+    }
+
+    /// Value of the hand under other ranking rules.
+    pub fn value_with(&self, rules: RankingRules) -> HandValue {
+        HandValue::with_rules(&self.cards, rules)
+    }
+
+    /// Value of the hand when every card of a rank in `wild` is wild, on
+    /// top of the jokers.
+    pub fn value_wild(&self, rules: RankingRules, wild: &[u8]) -> HandValue {
+        HandValue::with_wild(&self.cards, rules, wild)
+    }
+}
+
+impl Display for Hand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write_cards(f, &self.cards)
+    }
+}
+
+/// Best five-card hand picked out of a larger set of cards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestHand {
+    cards: Vec<Card>,
+    value: HandValue,
+}
+
+impl BestHand {
+    /// The five cards making up the hand.
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn value(&self) -> &HandValue {
+        &self.value
+    }
+}
+
+impl Display for BestHand {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write_cards(f, &self.cards)
+    }
+}
+
+/// Picks the strongest five-card combination out of five or more cards.
+pub(crate) fn best_five(cards: &[Card]) -> BestHand {
+    best_five_with(cards, RankingRules::High)
+}
+
+/// Same as [`best_five`], under the given ranking rules.
+pub(crate) fn best_five_with(cards: &[Card], rules: RankingRules) -> BestHand {
+    combinations(cards, HAND_SIZE)
+        .into_iter()
+        .map(|cards| BestHand {
+            value: HandValue::with_rules(&cards, rules),
+            cards,
+        })
+        .max_by(|a, b| a.value.cmp(&b.value))
         .unwrap()
-        .rank
-        .main;
+}
 
-    let mut winners: Vec<&Hand> = poker_hands
-        .iter()
-        .filter(|hand| hand.rank.main == highest)
+/// Every way of choosing `k` items out of `items`, preserving their order.
+pub(crate) fn combinations<T: Copy>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+
+    if items.len() < k {
+        return vec![];
+    }
+
+    let mut with_first: Vec<Vec<T>> = combinations(&items[1..], k - 1)
+        .into_iter()
+        .map(|mut rest| {
+            rest.insert(0, items[0]);
+            rest
+        })
         .collect();
 
-    if winners.len() > 1 {
-        let highest_major = winners
-            .iter()
-            .max_by_key(|hand| hand.rank.major)
-            .unwrap()
-            .rank
-            .major;
+    with_first.extend(combinations(&items[1..], k));
+    with_first
+}
+
+/// Parses any number of space-separated cards, rejecting duplicates. Jokers,
+/// written `JK`, may appear more than once.
+pub fn parse_cards(cards: &str) -> Result<Vec<Card>, HandParseError> {
+    let mut parsed: Vec<Card> = vec![];
+
+    for (position, token) in cards.split_whitespace().enumerate() {
+        let card = Card::parse(token, position)?;
 
-        winners.retain(|hand| hand.rank.major == highest_major);
+        if !card.is_joker() && parsed.contains(&card) {
+            return Err(HandParseError::DuplicateCard {
+                token: token.to_string(),
+                position,
+            });
+        }
+
+        parsed.push(card);
     }
 
-    if winners.len() > 1 {
-        let highest_minor = winners
-            .iter()
-            .max_by_key(|hand| hand.rank.minor)
-            .unwrap()
-            .rank
-            .minor;
+    Ok(parsed)
+}
 
-        winners.retain(|hand| hand.rank.minor == highest_minor);
+/// Parses a hand of five space-separated cards such as `"4S 5S 7H 8D JC"`,
+/// `"4s 5s 7h 8d Jc"` or `"4♠ 5♠ 7♥ 8♦ J♣"`.
+pub fn parse_hand(hand: &str) -> Result<Hand, HandParseError> {
+    let cards = parse_cards(hand)?;
+
+    if cards.len() != HAND_SIZE {
+        return Err(HandParseError::WrongCardCount {
+            hand: hand.to_string(),
+            count: cards.len(),
+            expected: HAND_SIZE,
+        });
     }
 
-    if winners.len() > 1 {
-        let count = winners[0].rank.left.len();
+    Ok(Hand::from(cards))
+}
 
-        for i in 0..count {
-            let highest_low = winners
-                .iter()
-                .max_by_key(|hand| hand.rank.left[i])
-                .unwrap()
-                .rank
-                .left[i];
+/// Same as [`parse_hand`], but refuses cards the deck of `rules` does not
+/// hold, such as the deuces through fives in short deck.
+pub fn parse_hand_with(hand: &str, rules: RankingRules) -> Result<Hand, HandParseError> {
+    let parsed = parse_hand(hand)?;
+    rules.check(hand, &parsed.cards)?;
 
-            winners.retain(|hand| hand.rank.left[i] == highest_low);
+    Ok(parsed)
+}
 
-            if winners.len() == 1 {
-                break;
-            }
-        }
+/// Evaluates a single hand.
+///
+/// # Panics
+///
+/// Panics if the hand cannot be parsed, see [`parse_hand`].
+pub fn evaluate(hand: &str) -> HandValue {
+    match parse_hand(hand) {
+        Ok(parsed) => parsed.value,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Evaluates a single hand under the given ranking rules.
+///
+/// # Panics
+///
+/// Panics if the hand cannot be parsed, see [`parse_hand_with`].
+pub fn evaluate_with(hand: &str, rules: RankingRules) -> HandValue {
+    match parse_hand_with(hand, rules) {
+        Ok(parsed) => parsed.value_with(rules),
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Evaluates a single hand with extra wild ranks, e.g. `&[2]` for deuces wild.
+///
+/// # Panics
+///
+/// Panics if the hand cannot be parsed, see [`parse_hand_with`].
+pub fn evaluate_wild(hand: &str, rules: RankingRules, wild: &[u8]) -> HandValue {
+    match parse_hand_with(hand, rules) {
+        Ok(parsed) => parsed.value_wild(rules, wild),
+        Err(err) => panic!("{}", err),
     }
+}
+
+/// Main
+///
+/// # Panics
+///
+/// Panics if any of the hands cannot be parsed, see [`try_winning_hands`].
+pub fn winning_hands<'a>(hands: &[&'a str]) -> Vec<&'a str> {
+    match try_winning_hands(hands) {
+        Ok(winners) => winners,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Same as [`winning_hands`], but reports the first hand that fails to parse.
+pub fn try_winning_hands<'a>(hands: &[&'a str]) -> Result<Vec<&'a str>, HandParseError> {
+    winning_hands_with(hands, RankingRules::High)
+}
+
+/// Same as [`try_winning_hands`], but refuses hands sharing a card, which
+/// cannot come from a single deck.
+pub fn winning_hands_strict<'a>(hands: &[&'a str]) -> Result<Vec<&'a str>, validate::DealError> {
+    validate::check_deal(hands, None)?;
+
+    Ok(try_winning_hands(hands)?)
+}
+
+/// Picks the winning hands under the given ranking rules.
+pub fn winning_hands_with<'a>(
+    hands: &[&'a str],
+    rules: RankingRules,
+) -> Result<Vec<&'a str>, HandParseError> {
+    winning_hands_wild(hands, rules, &[])
+}
+
+/// Picks the winning hands when the ranks in `wild` are wild, on top of the jokers.
+pub fn winning_hands_wild<'a>(
+    hands: &[&'a str],
+    rules: RankingRules,
+    wild: &[u8],
+) -> Result<Vec<&'a str>, HandParseError> {
+    let poker_hands: Vec<(&'a str, HandValue)> = hands
+        .iter()
+        .map(|&hand| {
+            parse_hand_with(hand, rules).map(|parsed| (hand, parsed.value_wild(rules, wild)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let highest = match poker_hands.iter().map(|(_, value)| value).max() {
+        Some(value) => value,
+        None => return Ok(vec![]),
+    };
 
-    winners
+    Ok(poker_hands
         .iter()
-        .map(|hand| *hands.get(hand.idx).unwrap())
-        .collect()
+        .filter(|(_, value)| value == highest)
+        .map(|&(hand, _)| hand)
+        .collect())
 }
@@ -0,0 +1,198 @@
+use crate::{Card, CardKind, HandValue, SUITS};
+use std::sync::OnceLock;
+
+const PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+const RANK_BITS: usize = 1 << 13;
+const SUIT_MASK: u32 = 0xF000;
+
+/// Card packed into 32 bits, Cactus Kev style:
+///
+/// ```text
+/// xxxbbbbb bbbbbbbb cdhsrrrr xxpppppp
+/// ```
+///
+/// with one `b` bit set for the rank, one `cdhs` bit for the suit, the rank
+/// index `r` (deuce = 0) and the rank's prime `p` (deuce = 2, ..., ace = 41).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PackedCard(u32);
+
+impl PackedCard {
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Jokers have no packed form and are handed back as the error.
+impl TryFrom<Card> for PackedCard {
+    type Error = Card;
+
+    fn try_from(card: Card) -> Result<Self, Self::Error> {
+        let suit = match card.kind {
+            CardKind::Club => 0x8000,
+            CardKind::Diamond => 0x4000,
+            CardKind::Heart => 0x2000,
+            CardKind::Spade => 0x1000,
+            CardKind::Joker => return Err(card),
+        };
+        let rank = (card.value - 2) as usize;
+
+        Ok(Self(
+            1 << (16 + rank) | suit | (rank as u32) << 8 | PRIMES[rank],
+        ))
+    }
+}
+
+struct Tables {
+    /// Strength of five suited cards, by rank bits.
+    flushes: Vec<u16>,
+    /// Strength of five distinct unsuited ranks, by rank bits.
+    unique: Vec<u16>,
+    /// Strength of hands with a repeated rank, by product of primes, sorted.
+    products: Vec<(u32, u16)>,
+    /// Value of each strength, lowest first.
+    values: Vec<HandValue>,
+}
+
+enum Key {
+    Flush(usize),
+    Unique(usize),
+    Product(u32),
+}
+
+impl Tables {
+    /// Ranks one hand of every equivalence class with the regular evaluator
+    /// and numbers the classes in that order.
+    fn build() -> Self {
+        let mut entries: Vec<(HandValue, Key)> = vec![];
+
+        for values in HandValue::rank_multisets(5, 2) {
+            if values.iter().all(|&value| value == values[0]) {
+                continue;
+            }
+
+            let offsuit: Vec<Card> = values
+                .iter()
+                .enumerate()
+                .map(|(idx, &value)| Card {
+                    kind: SUITS[idx % SUITS.len()],
+                    value,
+                })
+                .collect();
+            let packed: Vec<u32> = offsuit.iter().map(|&card| pack(card)).collect();
+            let bits = packed.iter().fold(0, |bits, card| bits | card) as usize >> 16;
+
+            let mut distinct = values.clone();
+            distinct.dedup();
+
+            if distinct.len() < values.len() {
+                let product = packed.iter().map(|card| card & 0xFF).product();
+                entries.push((HandValue::from_cards(&offsuit), Key::Product(product)));
+                continue;
+            }
+
+            let suited: Vec<Card> = values
+                .iter()
+                .map(|&value| Card {
+                    kind: CardKind::Spade,
+                    value,
+                })
+                .collect();
+
+            entries.push((HandValue::from_cards(&offsuit), Key::Unique(bits)));
+            entries.push((HandValue::from_cards(&suited), Key::Flush(bits)));
+        }
+
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut tables = Self {
+            flushes: vec![0; RANK_BITS],
+            unique: vec![0; RANK_BITS],
+            products: vec![],
+            values: vec![],
+        };
+
+        for (value, key) in entries {
+            if tables.values.last() != Some(&value) {
+                tables.values.push(value);
+            }
+
+            let strength = tables.values.len() as u16;
+
+            match key {
+                Key::Flush(bits) => tables.flushes[bits] = strength,
+                Key::Unique(bits) => tables.unique[bits] = strength,
+                Key::Product(product) => tables.products.push((product, strength)),
+            }
+        }
+
+        tables.products.sort_unstable();
+        tables
+    }
+}
+
+fn pack(card: Card) -> u32 {
+    PackedCard::try_from(card).unwrap().0
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+
+    TABLES.get_or_init(Tables::build)
+}
+
+/// Number of distinct five-card hand strengths.
+pub fn classes() -> usize {
+    tables().values.len()
+}
+
+/// Strength of five distinct cards, from 1 for `7-5-4-3-2` offsuit up to
+/// [`classes`] for a royal flush. Orders hands exactly like [`HandValue`].
+pub fn evaluate(cards: &[PackedCard; 5]) -> u16 {
+    let tables = tables();
+    let [a, b, c, d, e] = cards.map(|card| card.0);
+    let bits = ((a | b | c | d | e) >> 16) as usize;
+
+    if a & b & c & d & e & SUIT_MASK != 0 {
+        return tables.flushes[bits];
+    }
+
+    match tables.unique[bits] {
+        0 => {
+            let product = (a & 0xFF) * (b & 0xFF) * (c & 0xFF) * (d & 0xFF) * (e & 0xFF);
+            let idx = tables
+                .products
+                .binary_search_by_key(&product, |&(product, _)| product)
+                .expect("five distinct cards");
+
+            tables.products[idx].1
+        }
+        strength => strength,
+    }
+}
+
+/// Strongest five-card strength out of five to seven cards, or `None` if
+/// there are fewer than five.
+pub fn best(cards: &[PackedCard]) -> Option<u16> {
+    let n = cards.len();
+    let mut strongest = None;
+
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    for e in d + 1..n {
+                        let hand = [cards[a], cards[b], cards[c], cards[d], cards[e]];
+                        strongest = strongest.max(Some(evaluate(&hand)));
+                    }
+                }
+            }
+        }
+    }
+
+    strongest
+}
+
+/// The [`HandValue`] a strength stands for, or `None` if no hand has it.
+pub fn value(strength: u16) -> Option<&'static HandValue> {
+    tables().values.get(usize::from(strength).checked_sub(1)?)
+}
@@ -0,0 +1,305 @@
+use poker::{parse_hand_with, rank_name, validate, HandValue, RankingRules};
+use std::io::{self, Read};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+usage: poker [--json] [--rules RULES] [FILE]
+
+Reads one hand per line from FILE, or stdin when FILE is missing or `-`.
+Blank lines separate showdowns. Prints the winners of each showdown.
+
+options:
+  --json         print the results as JSON
+  --rules RULES  high (default), short-deck, ace-to-five or deuce-to-seven
+  -h, --help     print this help
+
+exit status: 0 on success, 1 on invalid hands, 2 on bad usage or unreadable input";
+
+struct Options {
+    json: bool,
+    rules: RankingRules,
+    path: Option<String>,
+}
+
+/// A winning hand, with the kicker that beat the runner-up if it came to that.
+struct Winner<'a> {
+    hand: &'a str,
+    value: HandValue,
+    kicker: Option<(u8, u8)>,
+}
+
+struct Outcome<'a> {
+    line: usize,
+    winners: Vec<Winner<'a>>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        json: false,
+        rules: RankingRules::High,
+        path: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--json" => options.json = true,
+            "--rules" => {
+                options.rules = match args.next().as_deref() {
+                    Some("high") => RankingRules::High,
+                    Some("short-deck") => RankingRules::ShortDeck,
+                    Some("ace-to-five") => RankingRules::AceToFive,
+                    Some("deuce-to-seven") => RankingRules::DeuceToSeven,
+                    Some(other) => return Err(format!("unknown rules `{}`", other)),
+                    None => return Err("--rules needs a value".to_string()),
+                }
+            }
+            "-" => options.path = None,
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{}`", flag)),
+            path if options.path.is_none() => options.path = Some(path.to_string()),
+            extra => return Err(format!("unexpected argument `{}`", extra)),
+        }
+    }
+
+    Ok(Some(options))
+}
+
+/// Groups the non-blank lines into showdowns, keeping 1-based line numbers.
+fn showdowns(input: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut groups: Vec<Vec<(usize, &str)>> = vec![vec![]];
+
+    for (idx, line) in input.lines().enumerate() {
+        match line.trim() {
+            "" => groups.push(vec![]),
+            hand => groups.last_mut().unwrap().push((idx + 1, hand)),
+        }
+    }
+
+    groups.retain(|group| !group.is_empty());
+    groups
+}
+
+fn decide<'a>(lines: &[(usize, &'a str)], rules: RankingRules) -> Result<Outcome<'a>, Vec<String>> {
+    let mut errors = vec![];
+    let mut values = vec![];
+
+    for &(line, hand) in lines {
+        match parse_hand_with(hand, rules) {
+            Ok(parsed) => values.push(parsed.value_with(rules)),
+            Err(err) => errors.push(format!("line {}: {}", line, err)),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let hands: Vec<&str> = lines.iter().map(|&(_, hand)| hand).collect();
+    let duplicates =
+        validate::find_duplicates(&hands, None).map_err(|err| vec![err.to_string()])?;
+
+    if !duplicates.is_empty() {
+        return Err(duplicates
+            .iter()
+            .map(|duplicate| {
+                let on: Vec<String> = duplicate
+                    .holders
+                    .iter()
+                    .map(|holder| match holder {
+                        validate::Holder::Hand(idx) => lines[*idx].0.to_string(),
+                        validate::Holder::Board => "the board".to_string(),
+                    })
+                    .collect();
+
+                format!("{} is dealt on lines {}", duplicate.card, on.join(", "))
+            })
+            .collect());
+    }
+
+    let best = values.iter().max().unwrap().clone();
+    let runner_up = values.iter().filter(|&value| value != &best).max();
+
+    let winners = lines
+        .iter()
+        .zip(values.iter())
+        .filter(|(_, value)| *value == &best)
+        .map(|(&(_, hand), value)| Winner {
+            hand,
+            value: value.clone(),
+            kicker: runner_up.and_then(|other| deciding_kicker(value, other)),
+        })
+        .collect();
+
+    Ok(Outcome {
+        line: lines[0].0,
+        winners,
+    })
+}
+
+/// The first kicker that differs, when both hands otherwise read the same.
+fn deciding_kicker(value: &HandValue, other: &HandValue) -> Option<(u8, u8)> {
+    let named = value.ranks().len() - value.kickers().len();
+
+    if value.category() != other.category() || value.ranks()[..named] != other.ranks()[..named] {
+        return None;
+    }
+
+    value
+        .ranks()
+        .iter()
+        .zip(other.ranks())
+        .skip(named)
+        .find(|(a, b)| a != b)
+        .map(|(&a, &b)| (a, b))
+}
+
+fn names(ranks: &[u8]) -> Vec<&'static str> {
+    ranks.iter().map(|&rank| rank_name(rank)).collect()
+}
+
+fn print_text(outcomes: &[Outcome]) {
+    for (idx, outcome) in outcomes.iter().enumerate() {
+        let result = match outcome.winners.len() {
+            1 => "winner",
+            _ => "split pot",
+        };
+
+        println!("Showdown {} (line {}): {}", idx + 1, outcome.line, result);
+
+        for winner in outcome.winners.iter() {
+            let mut explanation = winner.value.description();
+            let kickers = names(winner.value.kickers());
+
+            if !kickers.is_empty() {
+                explanation += &format!(", kickers {}", kickers.join(", "));
+            }
+
+            if let Some((mine, theirs)) = winner.kicker {
+                explanation += &format!(
+                    "; wins on kicker {} against {}",
+                    rank_name(mine),
+                    rank_name(theirs)
+                );
+            }
+
+            println!("  {}: {}", winner.hand, explanation);
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+fn json_list(items: &[&str]) -> String {
+    let quoted: Vec<String> = items.iter().map(|item| json_string(item)).collect();
+
+    format!("[{}]", quoted.join(","))
+}
+
+fn print_json(outcomes: &[Outcome]) {
+    let showdowns: Vec<String> = outcomes
+        .iter()
+        .map(|outcome| {
+            let winners: Vec<String> = outcome
+                .winners
+                .iter()
+                .map(|winner| {
+                    let kicker = match winner.kicker {
+                        Some((mine, theirs)) => format!(
+                            "{{\"winner\":{},\"runner_up\":{}}}",
+                            json_string(rank_name(mine)),
+                            json_string(rank_name(theirs))
+                        ),
+                        None => "null".to_string(),
+                    };
+
+                    format!(
+                        "{{\"hand\":{},\"category\":{},\"description\":{},\"kickers\":{},\"deciding_kicker\":{}}}",
+                        json_string(winner.hand),
+                        json_string(&winner.value.category().to_string()),
+                        json_string(&winner.value.description()),
+                        json_list(&names(winner.value.kickers())),
+                        kicker
+                    )
+                })
+                .collect();
+
+            format!(
+                "{{\"line\":{},\"split\":{},\"winners\":[{}]}}",
+                outcome.line,
+                outcome.winners.len() > 1,
+                winners.join(",")
+            )
+        })
+        .collect();
+
+    println!("{{\"showdowns\":[{}]}}", showdowns.join(","));
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("poker: {}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let input = match &options.path {
+        Some(path) => std::fs::read_to_string(path),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map(|_| input)
+        }
+    };
+
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("poker: cannot read input: {}", err);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut outcomes = vec![];
+    let mut errors = vec![];
+
+    for lines in showdowns(&input) {
+        match decide(&lines, options.rules) {
+            Ok(outcome) => outcomes.push(outcome),
+            Err(mut invalid) => errors.append(&mut invalid),
+        }
+    }
+
+    if !errors.is_empty() {
+        for err in errors {
+            eprintln!("poker: {}", err);
+        }
+
+        return ExitCode::from(1);
+    }
+
+    match options.json {
+        true => print_json(&outcomes),
+        false => print_text(&outcomes),
+    }
+
+    ExitCode::SUCCESS
+}
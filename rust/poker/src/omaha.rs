@@ -0,0 +1,116 @@
+use crate::holdem::{parse_board, parse_hole};
+use crate::{combinations, BestHand, Card, HandParseError, HandRank, HandValue, RankingRules};
+
+const HOLE_CARDS: [usize; 2] = [4, 5];
+const FROM_HOLE: usize = 2;
+const FROM_BOARD: usize = 3;
+const LOW_QUALIFIER: u8 = 8;
+
+/// Every legal five-card hand: exactly two hole cards with exactly three board cards.
+fn hands(hole: &[Card], board: &[Card]) -> Vec<Vec<Card>> {
+    let boards = combinations(board, FROM_BOARD);
+
+    combinations(hole, FROM_HOLE)
+        .into_iter()
+        .flat_map(|pair| {
+            boards.iter().map(move |three| {
+                let mut cards = pair.clone();
+                cards.extend(three);
+                cards
+            })
+        })
+        .collect()
+}
+
+fn parse_deal(hole: &str, board: &str) -> Result<(Vec<Card>, Vec<Card>), HandParseError> {
+    let hole = parse_hole(hole, &HOLE_CARDS)?;
+    let board = parse_board(board, &hole)?;
+
+    Ok((hole, board))
+}
+
+/// Winners of an Omaha Hi/Lo pot. `low` is empty when nobody qualifies, in
+/// which case the high hands scoop the whole pot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HiLo<'a> {
+    pub high: Vec<&'a str>,
+    pub low: Vec<&'a str>,
+}
+
+fn best_with(
+    hole: &[Card],
+    board: &[Card],
+    rules: RankingRules,
+    qualifies: impl Fn(&HandValue) -> bool,
+) -> Option<BestHand> {
+    hands(hole, board)
+        .into_iter()
+        .map(|cards| BestHand {
+            value: HandValue::with_rules(&cards, rules),
+            cards,
+        })
+        .filter(|best| qualifies(&best.value))
+        .max_by(|a, b| a.value.cmp(&b.value))
+}
+
+/// Best high hand out of cards already parsed, or `None` without at least
+/// two hole cards and three on the board.
+pub(crate) fn best_high(hole: &[Card], board: &[Card]) -> Option<BestHand> {
+    best_with(hole, board, RankingRules::High, |_| true)
+}
+
+/// Best high hand from four (or five, for PLO5) hole cards and the board.
+pub fn best_hand(hole: &str, board: &str) -> Result<BestHand, HandParseError> {
+    let (hole, board) = parse_deal(hole, board)?;
+
+    Ok(best_high(&hole, &board).unwrap())
+}
+
+/// Best eight-or-better low, ranked ace-to-five, if the hole cards and board
+/// can make one.
+pub fn best_low(hole: &str, board: &str) -> Result<Option<BestHand>, HandParseError> {
+    let (hole, board) = parse_deal(hole, board)?;
+
+    Ok(best_with(&hole, &board, RankingRules::AceToFive, |value| {
+        value.category() == HandRank::HighCard && value.ranks()[0] <= LOW_QUALIFIER
+    }))
+}
+
+fn winners<'a, T: Ord>(hands: &[(&'a str, T)]) -> Vec<&'a str> {
+    let best = match hands.iter().map(|(_, hand)| hand).max() {
+        Some(hand) => hand,
+        None => return vec![],
+    };
+
+    hands
+        .iter()
+        .filter(|(_, hand)| hand == best)
+        .map(|&(hole, _)| hole)
+        .collect()
+}
+
+/// Returns the hole cards of every player sharing the best high hand.
+pub fn showdown<'a>(players: &[&'a str], board: &str) -> Result<Vec<&'a str>, HandParseError> {
+    let hands: Vec<(&'a str, HandValue)> = players
+        .iter()
+        .map(|&hole| best_hand(hole, board).map(|best| (hole, best.value)))
+        .collect::<Result<_, _>>()?;
+
+    Ok(winners(&hands))
+}
+
+/// Splits the pot between the best high hands and the best qualifying lows.
+pub fn hi_lo_showdown<'a>(players: &[&'a str], board: &str) -> Result<HiLo<'a>, HandParseError> {
+    let mut lows: Vec<(&'a str, HandValue)> = vec![];
+
+    for &hole in players {
+        if let Some(low) = best_low(hole, board)? {
+            lows.push((hole, low.value));
+        }
+    }
+
+    Ok(HiLo {
+        high: showdown(players, board)?,
+        low: winners(&lows),
+    })
+}
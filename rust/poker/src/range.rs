@@ -0,0 +1,470 @@
+use crate::deck::{Deck, Rng};
+use crate::equity::{Deal, Equity, Method, Tally, DEFAULT_TRIALS, EXHAUSTIVE_LIMIT};
+use crate::{parse_cards, Card, HandParseError, RankingRules, SUITS};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+const BOARD_CARDS: usize = 5;
+
+/**
+ * Errors
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeError {
+    /// A comma-separated part of the notation that does not describe hands.
+    InvalidToken { token: String },
+    /// Board or dead cards that cannot be used.
+    Cards(HandParseError),
+    /// Every combination of the player's range is blocked by known cards.
+    EmptyRange { player: usize },
+    /// No combinations of the ranges can be dealt together.
+    NoDeal,
+    /// The board needs more cards than the ranges leave in the deck.
+    NotEnoughCards { needed: usize, left: usize },
+}
+
+impl Display for RangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::InvalidToken { token } => write!(f, "invalid range token `{}`", token),
+            Self::Cards(err) => write!(f, "{}", err),
+            Self::EmptyRange { player } => {
+                write!(
+                    f,
+                    "every hand in range {} is blocked by known cards",
+                    player
+                )
+            }
+            Self::NoDeal => write!(f, "the ranges cannot be dealt together"),
+            Self::NotEnoughCards { needed, left } => {
+                write!(f, "{} cards are needed but {} are left", needed, left)
+            }
+        }
+    }
+}
+
+impl Error for RangeError {}
+
+impl From<HandParseError> for RangeError {
+    fn from(err: HandParseError) -> Self {
+        Self::Cards(err)
+    }
+}
+
+/**
+ * Notation
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Suitedness {
+    Pair,
+    Suited,
+    Offsuit,
+    Any,
+}
+
+/// A starting hand class such as `QQ`, `AKs` or `KQo`, high rank first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Class {
+    high: u8,
+    low: u8,
+    suits: Suitedness,
+}
+
+impl Class {
+    fn parse(text: &str) -> Option<Self> {
+        let chars: Vec<char> = text.chars().collect();
+
+        let (ranks, suffix) = match chars.len() {
+            2 => (&chars[..], None),
+            3 => (&chars[..2], Some(chars[2].to_ascii_lowercase())),
+            _ => return None,
+        };
+
+        let first = Card::parse_value(&ranks[0].to_uppercase().to_string())?;
+        let second = Card::parse_value(&ranks[1].to_uppercase().to_string())?;
+
+        let suits = match (first == second, suffix) {
+            (true, None) => Suitedness::Pair,
+            (true, Some(_)) => return None,
+            (false, None) => Suitedness::Any,
+            (false, Some('s')) => Suitedness::Suited,
+            (false, Some('o')) => Suitedness::Offsuit,
+            (false, Some(_)) => return None,
+        };
+
+        Some(Self {
+            high: first.max(second),
+            low: first.min(second),
+            suits,
+        })
+    }
+
+    fn with_low(self, low: u8) -> Self {
+        match self.suits {
+            Suitedness::Pair => Self {
+                high: low,
+                low,
+                ..self
+            },
+            _ => Self { low, ..self },
+        }
+    }
+
+    /// `QQ+` is every pair from queens up, `A5s+` every suited ace from
+    /// the five up to the king.
+    fn and_up(self) -> Vec<Self> {
+        let top = match self.suits {
+            Suitedness::Pair => 14,
+            _ => self.high - 1,
+        };
+
+        (self.low..=top).map(|low| self.with_low(low)).collect()
+    }
+
+    /// `QQ-99` or `A5s-A2s`: both ends must share the high card and suits.
+    fn span(self, other: Self) -> Option<Vec<Self>> {
+        let same_high = self.suits == Suitedness::Pair || self.high == other.high;
+
+        if self.suits != other.suits || !same_high {
+            return None;
+        }
+
+        let (from, to) = (self.low.min(other.low), self.low.max(other.low));
+
+        Some((from..=to).map(|low| self.with_low(low)).collect())
+    }
+
+    fn combos(&self) -> Vec<[Card; 2]> {
+        let mut combos = vec![];
+
+        for (i, &first) in SUITS.iter().enumerate() {
+            for (j, &second) in SUITS.iter().enumerate() {
+                let keep = match self.suits {
+                    Suitedness::Pair => i < j,
+                    Suitedness::Suited => i == j,
+                    Suitedness::Offsuit => i != j,
+                    Suitedness::Any => true,
+                };
+
+                if keep {
+                    combos.push(combo(
+                        Card {
+                            kind: first,
+                            value: self.high,
+                        },
+                        Card {
+                            kind: second,
+                            value: self.low,
+                        },
+                    ));
+                }
+            }
+        }
+
+        combos
+    }
+}
+
+/// Exact hole cards such as `AhKh`, higher card first.
+fn exact(text: &str) -> Option<[Card; 2]> {
+    let split = text.char_indices().nth(2)?.0;
+    let first = Card::parse(&text[..split], 0).ok()?;
+    let second = Card::parse(&text[split..], 1).ok()?;
+
+    if first == second || first.is_joker() || second.is_joker() {
+        return None;
+    }
+
+    Some(combo(first, second))
+}
+
+/// Two hole cards in the one order a range keeps them: the higher card
+/// first, and the later suit first within a pair.
+fn combo(first: Card, second: Card) -> [Card; 2] {
+    let order = |card: &Card| {
+        let suit = SUITS.iter().position(|&kind| kind == card.kind);
+        (card.value, suit)
+    };
+
+    match order(&first) > order(&second) {
+        true => [first, second],
+        false => [second, first],
+    }
+}
+
+fn expand(token: &str) -> Option<Vec<[Card; 2]>> {
+    if let Some(combo) = exact(token) {
+        return Some(vec![combo]);
+    }
+
+    let classes = match (token.strip_suffix('+'), token.split_once('-')) {
+        (Some(class), None) => Class::parse(class)?.and_up(),
+        (None, Some((from, to))) => Class::parse(from)?.span(Class::parse(to)?)?,
+        (None, None) => vec![Class::parse(token)?],
+        (Some(_), Some(_)) => return None,
+    };
+
+    Some(classes.iter().flat_map(|class| class.combos()).collect())
+}
+
+/**
+ * Ranges
+ */
+/// Every hole-card combination a player may hold, each counted once.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Range {
+    combos: Vec<[Card; 2]>,
+}
+
+impl Range {
+    pub fn combos(&self) -> &[[Card; 2]] {
+        &self.combos
+    }
+
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Card removal: drops every combination holding one of the given cards.
+    pub fn remove(&mut self, cards: &[Card]) {
+        self.combos
+            .retain(|combo| !combo.iter().any(|card| cards.contains(card)));
+    }
+}
+
+/// Expands comma-separated range notation: pairs (`QQ`), suited (`AKs`),
+/// offsuit (`KQo`) or both (`AK`), optionally followed by `+` for every
+/// hand up to the top, spans such as `QQ-99` or `A5s-A2s`, and exact hole
+/// cards such as `AhKh`. Overlapping parts are counted once.
+pub fn parse(notation: &str) -> Result<Range, RangeError> {
+    let mut range = Range::default();
+
+    for token in notation.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        let combos = expand(token).ok_or_else(|| RangeError::InvalidToken {
+            token: token.to_string(),
+        })?;
+
+        for combo in combos {
+            if !range.combos.contains(&combo) {
+                range.combos.push(combo);
+            }
+        }
+    }
+
+    Ok(range)
+}
+
+/**
+ * Equity
+ */
+struct Matchup {
+    ranges: Vec<Range>,
+    board: Vec<Card>,
+    dead: Vec<Card>,
+}
+
+impl Matchup {
+    fn parse(ranges: &[&str], board: &str, dead: &str) -> Result<Self, RangeError> {
+        let board_cards = parse_cards(board)?;
+
+        if board_cards.len() > BOARD_CARDS {
+            return Err(RangeError::Cards(HandParseError::WrongCardCount {
+                hand: board.to_string(),
+                count: board_cards.len(),
+                expected: BOARD_CARDS,
+            }));
+        }
+
+        let dead_cards = parse_cards(dead)?;
+
+        if let Some(position) = dead_cards
+            .iter()
+            .position(|card| board_cards.contains(card))
+        {
+            return Err(RangeError::Cards(HandParseError::DuplicateCard {
+                token: dead.split_whitespace().nth(position).unwrap().to_string(),
+                position,
+            }));
+        }
+
+        let known: Vec<Card> = board_cards
+            .iter()
+            .chain(dead_cards.iter())
+            .copied()
+            .collect();
+
+        let ranges = ranges
+            .iter()
+            .enumerate()
+            .map(|(player, notation)| {
+                let mut range = parse(notation)?;
+                range.remove(&known);
+
+                match range.is_empty() {
+                    true => Err(RangeError::EmptyRange { player }),
+                    false => Ok(range),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (needed, left) = (
+            BOARD_CARDS - board_cards.len(),
+            Deck::new()
+                .len()
+                .saturating_sub(known.len() + 2 * ranges.len()),
+        );
+
+        if left < needed {
+            return Err(RangeError::NotEnoughCards { needed, left });
+        }
+
+        let matchup = Self {
+            ranges,
+            board: board_cards,
+            dead: dead_cards,
+        };
+
+        match matchup.first_deal(&mut vec![]) {
+            true => Ok(matchup),
+            false => Err(RangeError::NoDeal),
+        }
+    }
+
+    fn deal(&self, holes: &[[Card; 2]]) -> Deal {
+        Deal::new(
+            holes.iter().map(|hole| hole.to_vec()).collect(),
+            self.board.clone(),
+            &self.dead,
+            RankingRules::High,
+        )
+    }
+
+    /// Whether some choice of combinations, one per range, shares no card.
+    fn first_deal(&self, picked: &mut Vec<[Card; 2]>) -> bool {
+        let range = match self.ranges.get(picked.len()) {
+            Some(range) => range,
+            None => return true,
+        };
+
+        for &combo in range.combos() {
+            if !collides(&combo, picked) {
+                picked.push(combo);
+
+                if self.first_deal(picked) {
+                    return true;
+                }
+
+                picked.pop();
+            }
+        }
+
+        false
+    }
+
+    /// Runs every deal of non-colliding combinations, each weighted equally.
+    fn every_deal(&self, picked: &mut Vec<[Card; 2]>, tally: &mut Tally) {
+        let range = match self.ranges.get(picked.len()) {
+            Some(range) => range,
+            None => return self.deal(picked).run_into(Method::Exhaustive, tally),
+        };
+
+        for &combo in range.combos() {
+            if !collides(&combo, picked) {
+                picked.push(combo);
+                self.every_deal(picked, tally);
+                picked.pop();
+            }
+        }
+    }
+
+    /// Upper bound on the deals times the runouts of each.
+    fn work(&self) -> u64 {
+        let holes = vec![Vec::new(); self.ranges.len()];
+        let runouts =
+            Deal::new(holes, self.board.clone(), &self.dead, RankingRules::High).runouts();
+
+        self.ranges.iter().fold(runouts, |work, range| {
+            work.saturating_mul(range.len() as u64)
+        })
+    }
+
+    fn run(&self, method: Method) -> Vec<Equity> {
+        let mut tally = Tally::new(self.ranges.len());
+
+        match method {
+            Method::Exhaustive => self.every_deal(&mut vec![], &mut tally),
+            Method::MonteCarlo { trials, seed } => {
+                let mut rng = Rng::new(seed);
+                let mut done = 0;
+
+                // Redrawing colliding deals keeps them uniformly weighted.
+                while done < trials {
+                    let holes: Vec<[Card; 2]> = self
+                        .ranges
+                        .iter()
+                        .map(|range| range.combos[rng.below(range.len())])
+                        .collect();
+
+                    let clash = (1..holes.len()).any(|idx| collides(&holes[idx], &holes[..idx]));
+
+                    if clash {
+                        continue;
+                    }
+
+                    let runout = Method::MonteCarlo {
+                        trials: 1,
+                        seed: rng.next_u64(),
+                    };
+
+                    self.deal(&holes).run_into(runout, &mut tally);
+                    done += 1;
+                }
+            }
+        }
+
+        tally.equities()
+    }
+}
+
+fn collides(combo: &[Card; 2], picked: &[[Card; 2]]) -> bool {
+    picked.iter().flatten().any(|card| combo.contains(card))
+}
+
+/// Hold'em equity of each range against the others, given the board so far
+/// and cards known to be out of play. Every combination of hole cards that
+/// can be dealt together counts once, so `AA` (6 combinations) weighs half
+/// as much as `AKo` (12). Deals and runouts are enumerated when there are
+/// at most [`EXHAUSTIVE_LIMIT`] of them, and sampled [`DEFAULT_TRIALS`]
+/// times from `seed` otherwise.
+pub fn equity(
+    ranges: &[&str],
+    board: &str,
+    dead: &str,
+    seed: u64,
+) -> Result<Vec<Equity>, RangeError> {
+    let matchup = Matchup::parse(ranges, board, dead)?;
+
+    let method = match matchup.work() <= EXHAUSTIVE_LIMIT {
+        true => Method::Exhaustive,
+        false => Method::MonteCarlo {
+            trials: DEFAULT_TRIALS,
+            seed,
+        },
+    };
+
+    Ok(matchup.run(method))
+}
+
+/// Same as [`equity`], with the method chosen by the caller.
+pub fn calculate(
+    ranges: &[&str],
+    board: &str,
+    dead: &str,
+    method: Method,
+) -> Result<Vec<Equity>, RangeError> {
+    Ok(Matchup::parse(ranges, board, dead)?.run(method))
+}
@@ -0,0 +1,176 @@
+use crate::HandValue;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/**
+ * Errors
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShowdownError {
+    /// Every player folded, so nobody can win the pot.
+    NoLivePlayers,
+    /// A player still in a contested pot has no hand to show.
+    MissingHand { seat: usize },
+    /// The odd-chip seat is not at the table.
+    InvalidSeat { seat: usize },
+}
+
+impl Display for ShowdownError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::NoLivePlayers => write!(f, "every player folded"),
+            Self::MissingHand { seat } => write!(f, "seat {} has no hand to show", seat),
+            Self::InvalidSeat { seat } => write!(f, "seat {} is not at the table", seat),
+        }
+    }
+}
+
+impl Error for ShowdownError {}
+
+/**
+ * Showdown
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Active,
+    AllIn,
+    Folded,
+}
+
+/// A seat at showdown. Seats are numbered by their index in the slice given
+/// to [`resolve`], going clockwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    /// Hand shown, not needed for folded players or an uncontested pot.
+    pub hand: Option<HandValue>,
+    /// Chips put in over the whole hand.
+    pub contribution: u64,
+    pub status: Status,
+}
+
+impl Player {
+    pub fn new(hand: Option<HandValue>, contribution: u64, status: Status) -> Self {
+        Self {
+            hand,
+            contribution,
+            status,
+        }
+    }
+
+    fn live(&self) -> bool {
+        self.status != Status::Folded
+    }
+}
+
+/// The main pot or a side pot, with the seats that may win it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pot {
+    pub amount: u64,
+    pub eligible: Vec<usize>,
+    pub winners: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    /// Main pot first, then each side pot.
+    pub pots: Vec<Pot>,
+    /// Chips won by each seat, uncalled bets included.
+    pub payouts: Vec<u64>,
+}
+
+/// Builds the main and side pots from each player's contribution and awards
+/// each to the best eligible hand. Split pots are shared evenly, and the odd
+/// chips go one at a time to the winners closest clockwise from
+/// `odd_chip_seat`, usually the first seat left of the button.
+pub fn resolve(players: &[Player], odd_chip_seat: usize) -> Result<Resolution, ShowdownError> {
+    if odd_chip_seat >= players.len() {
+        return Err(ShowdownError::InvalidSeat {
+            seat: odd_chip_seat,
+        });
+    }
+
+    let mut levels: Vec<u64> = players
+        .iter()
+        .filter(|player| player.live())
+        .map(|player| player.contribution)
+        .collect();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let top = *levels.last().ok_or(ShowdownError::NoLivePlayers)?;
+    let mut pots: Vec<Pot> = vec![];
+    let mut floor = 0;
+
+    for level in levels {
+        // Folded chips above the last live level still belong in the last pot.
+        let ceiling = match level == top {
+            true => u64::MAX,
+            false => level,
+        };
+        let amount = players
+            .iter()
+            .map(|player| player.contribution.clamp(floor, ceiling) - floor)
+            .sum();
+        let eligible: Vec<usize> = (0..players.len())
+            .filter(|&seat| players[seat].live() && players[seat].contribution >= level)
+            .collect();
+
+        floor = level;
+
+        if amount == 0 {
+            continue;
+        }
+
+        match pots.last_mut() {
+            Some(last) if last.eligible == eligible => last.amount += amount,
+            _ => pots.push(Pot {
+                amount,
+                eligible,
+                winners: vec![],
+            }),
+        }
+    }
+
+    let mut payouts = vec![0; players.len()];
+
+    for pot in pots.iter_mut() {
+        pot.winners = winners(players, &pot.eligible)?;
+
+        let share = pot.amount / pot.winners.len() as u64;
+        let odd = (pot.amount % pot.winners.len() as u64) as usize;
+
+        let mut order = pot.winners.clone();
+        order.sort_by_key(|&seat| (seat + players.len() - odd_chip_seat) % players.len());
+
+        for (idx, &seat) in order.iter().enumerate() {
+            payouts[seat] += share + u64::from(idx < odd);
+        }
+    }
+
+    Ok(Resolution { pots, payouts })
+}
+
+fn winners(players: &[Player], eligible: &[usize]) -> Result<Vec<usize>, ShowdownError> {
+    if eligible.len() == 1 {
+        return Ok(eligible.to_vec());
+    }
+
+    let hands = eligible
+        .iter()
+        .map(|&seat| {
+            players[seat]
+                .hand
+                .as_ref()
+                .map(|hand| (seat, hand))
+                .ok_or(ShowdownError::MissingHand { seat })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let best = hands.iter().map(|&(_, hand)| hand).max().unwrap();
+
+    Ok(hands
+        .iter()
+        .filter(|&&(_, hand)| hand == best)
+        .map(|&(seat, _)| seat)
+        .collect())
+}
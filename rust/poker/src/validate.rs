@@ -0,0 +1,116 @@
+use crate::{parse_cards, Card, HandParseError};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Where a card was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Holder {
+    /// Index of the hand in the list given.
+    Hand(usize),
+    Board,
+}
+
+impl Display for Holder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Hand(idx) => write!(f, "hand {}", idx),
+            Self::Board => write!(f, "the board"),
+        }
+    }
+}
+
+/// A card dealt more than once, with everything holding it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Duplicate {
+    pub card: Card,
+    pub holders: Vec<Holder>,
+}
+
+/// Why a set of hands cannot come from a single deck.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DealError {
+    Parse(HandParseError),
+    Duplicates(Vec<Duplicate>),
+}
+
+impl Display for DealError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Parse(err) => write!(f, "{}", err),
+            Self::Duplicates(duplicates) => {
+                for (idx, duplicate) in duplicates.iter().enumerate() {
+                    let holders: Vec<String> = duplicate
+                        .holders
+                        .iter()
+                        .map(|holder| holder.to_string())
+                        .collect();
+
+                    if idx > 0 {
+                        write!(f, "; ")?;
+                    }
+
+                    write!(f, "{} held by {}", duplicate.card, holders.join(", "))?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for DealError {}
+
+impl From<HandParseError> for DealError {
+    fn from(err: HandParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+/// Finds every card that appears in more than one of the hands and the
+/// optional board, in the order first seen. Jokers are never duplicates.
+pub fn find_duplicates(
+    hands: &[&str],
+    board: Option<&str>,
+) -> Result<Vec<Duplicate>, HandParseError> {
+    let mut sources: Vec<(Holder, Vec<Card>)> = hands
+        .iter()
+        .enumerate()
+        .map(|(idx, hand)| parse_cards(hand).map(|cards| (Holder::Hand(idx), cards)))
+        .collect::<Result<_, _>>()?;
+
+    if let Some(board) = board {
+        sources.push((Holder::Board, parse_cards(board)?));
+    }
+
+    Ok(duplicates(sources))
+}
+
+/// Same as [`find_duplicates`], over cards already parsed.
+pub(crate) fn duplicates(sources: Vec<(Holder, Vec<Card>)>) -> Vec<Duplicate> {
+    let mut seen: Vec<Duplicate> = vec![];
+
+    for (holder, cards) in sources {
+        for card in cards.into_iter().filter(|card| !card.is_joker()) {
+            match seen.iter_mut().find(|duplicate| duplicate.card == card) {
+                Some(duplicate) => duplicate.holders.push(holder),
+                None => seen.push(Duplicate {
+                    card,
+                    holders: vec![holder],
+                }),
+            }
+        }
+    }
+
+    seen.retain(|duplicate| duplicate.holders.len() > 1);
+    seen
+}
+
+/// Fails with every duplicated card, if there are any.
+pub fn check_deal(hands: &[&str], board: Option<&str>) -> Result<(), DealError> {
+    let duplicates = find_duplicates(hands, board)?;
+
+    match duplicates.is_empty() {
+        true => Ok(()),
+        false => Err(DealError::Duplicates(duplicates)),
+    }
+}
@@ -1,4 +1,16 @@
-use poker::winning_hands;
+// The upstream `test` helper names its lifetimes.
+#![allow(clippy::needless_lifetimes)]
+
+use poker::deck::{Deck, Street};
+use poker::lookup::{self, PackedCard};
+use poker::range::{self, RangeError};
+use poker::showdown::{self, Player, Pot, ShowdownError, Status};
+use poker::validate::{self, DealError, Duplicate, Holder};
+use poker::{
+    equity, evaluate, evaluate_wild, evaluate_with, history, holdem, omaha, parse_cards,
+    parse_hand, parse_hand_with, try_winning_hands, winning_hands, winning_hands_strict,
+    winning_hands_wild, winning_hands_with, Card, HandParseError, HandRank, RankingRules,
+};
 use std::collections::HashSet;
 
 fn hs_from<'a>(input: &[&'a str]) -> HashSet<&'a str> {
@@ -231,4 +243,1405 @@ mod tests {
         // even though an ace is usually high, a 5-high straight flush is the lowest-scoring straight flush
         test(&["2H 3H 4H 5H 6H", "4D AD 3D 2D 5D"], &["2H 3H 4H 5H 6H"])
     }
+
+    #[test]
+    fn test_parse_hand_keeps_cards() {
+        let hand = parse_hand("4S 5S 7H 8D 10C").unwrap();
+        let values: Vec<u8> = hand.cards().iter().map(|card| card.value()).collect();
+        assert_eq!(values, vec![4, 5, 7, 8, 10])
+    }
+
+    #[test]
+    fn test_parse_hand_rejects_unknown_rank() {
+        assert_eq!(
+            parse_hand("4S 5S 1H 8D JC").unwrap_err(),
+            HandParseError::InvalidRank {
+                token: "1H".to_string(),
+                position: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_parse_hand_rejects_unknown_suit() {
+        assert_eq!(
+            parse_hand("4S 5S 7H 8D JX").unwrap_err(),
+            HandParseError::InvalidSuit {
+                token: "JX".to_string(),
+                position: 4
+            }
+        )
+    }
+
+    #[test]
+    fn test_parse_hand_rejects_wrong_card_count() {
+        assert_eq!(
+            parse_hand("4S 5S 7H 8D").unwrap_err(),
+            HandParseError::WrongCardCount {
+                hand: "4S 5S 7H 8D".to_string(),
+                count: 4,
+                expected: 5
+            }
+        );
+        assert_eq!(
+            parse_hand("").unwrap_err(),
+            HandParseError::WrongCardCount {
+                hand: "".to_string(),
+                count: 0,
+                expected: 5
+            }
+        )
+    }
+
+    #[test]
+    fn test_parse_hand_rejects_duplicate_card() {
+        assert_eq!(
+            parse_hand("4S 5S 7H 4S JC").unwrap_err(),
+            HandParseError::DuplicateCard {
+                token: "4S".to_string(),
+                position: 3
+            }
+        )
+    }
+
+    #[test]
+    fn test_try_winning_hands_reports_invalid_hand() {
+        assert_eq!(
+            try_winning_hands(&["4S 5S 7H 8D JC", "2S 4C 7S 9H 0H"]),
+            Err(HandParseError::InvalidRank {
+                token: "0H".to_string(),
+                position: 4
+            })
+        )
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_winning_hands_panics_on_invalid_hand() {
+        winning_hands(&["4S 5S 7H 8D JC", "2S 4C 7S 9H"]);
+    }
+
+    #[test]
+    fn test_evaluate_reports_category_and_kickers() {
+        let value = evaluate("4S 4H KC 9D 2H");
+        assert_eq!(value.category(), HandRank::OnePair);
+        assert_eq!(value.ranks(), &[4, 13, 9, 2]);
+        assert_eq!(value.description(), "One pair, Fours")
+    }
+
+    #[test]
+    fn test_evaluate_describes_full_house() {
+        let value = evaluate("KS 4H KC 4D KH");
+        assert_eq!(value.category(), HandRank::FullHouse);
+        assert_eq!(value.ranks(), &[13, 4]);
+        assert_eq!(value.to_string(), "Full house, Kings over Fours")
+    }
+
+    #[test]
+    fn test_evaluate_wheel_is_five_high() {
+        let value = evaluate("4D AH 3S 2D 5C");
+        assert_eq!(value.category(), HandRank::Straight);
+        assert_eq!(value.ranks(), &[5]);
+        assert_eq!(value.description(), "Straight, Five high")
+    }
+
+    #[test]
+    fn test_evaluate_descriptions() {
+        assert_eq!(
+            evaluate("2S 8H 2D 8D 3H").description(),
+            "Two pair, Eights and Twos"
+        );
+        assert_eq!(
+            evaluate("6S 6H 6D 8D 3H").description(),
+            "Three of a kind, Sixes"
+        );
+        assert_eq!(evaluate("4H 7H 8H 9H 6H").description(), "Flush, Nine high");
+        assert_eq!(evaluate("10C JC QC KC AC").description(), "Royal flush");
+        assert_eq!(evaluate("2S 4C 7S 9H 10H").description(), "High card, Ten")
+    }
+
+    #[test]
+    fn test_hand_values_sort_by_strength() {
+        let mut values = [
+            evaluate("2S 8H 2D 8D 3H"),
+            evaluate("4S 5H 4C 8D 4H"),
+            evaluate("JD QH JS 8D QC"),
+            evaluate("3S 4S 5D 6H JH"),
+        ];
+        values.sort();
+
+        let categories: Vec<HandRank> = values.iter().map(|value| value.category()).collect();
+        assert_eq!(
+            categories,
+            vec![
+                HandRank::HighCard,
+                HandRank::TwoPair,
+                HandRank::TwoPair,
+                HandRank::ThreeOfKind
+            ]
+        );
+        assert_eq!(values[1].ranks(), &[8, 2, 3]);
+        assert_eq!(evaluate("3S 4S 5D 6H JH"), evaluate("3H 4H 5C 6C JD"))
+    }
+
+    #[test]
+    fn test_holdem_best_hand_reports_cards_used() {
+        let best = holdem::best_hand("AH 7C", "KH 2H 9H 7D QH").unwrap();
+        assert_eq!(best.value().category(), HandRank::Flush);
+        assert_eq!(best.value().ranks(), &[14, 13, 12, 9, 2]);
+        assert_eq!(best.cards(), parse_cards("AH KH 2H 9H QH").unwrap())
+    }
+
+    #[test]
+    fn test_holdem_best_hand_on_the_flop() {
+        let best = holdem::best_hand("5S 5D", "5C KD KS").unwrap();
+        assert_eq!(best.value().description(), "Full house, Fives over Kings")
+    }
+
+    #[test]
+    fn test_holdem_showdown_kicker_decides() {
+        assert_eq!(
+            holdem::showdown(&["AS QD", "AC JH", "8C 8D"], "AD 9S 6H 3C 2D").unwrap(),
+            vec!["AS QD"]
+        )
+    }
+
+    #[test]
+    fn test_holdem_showdown_board_plays_splits_pot() {
+        assert_eq!(
+            holdem::showdown(&["2C 3D", "4H 2S", "AH 7D"], "10S JS QD KC AS").unwrap(),
+            vec!["2C 3D", "4H 2S", "AH 7D"]
+        )
+    }
+
+    #[test]
+    fn test_holdem_rejects_wrong_hole_card_count() {
+        assert_eq!(
+            holdem::best_hand("AS KD QC", "2C 3D 4H 5S 9C").unwrap_err(),
+            HandParseError::WrongCardCount {
+                hand: "AS KD QC".to_string(),
+                count: 3,
+                expected: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_holdem_rejects_card_in_hole_and_board() {
+        assert_eq!(
+            holdem::best_hand("AS KD", "2C 3D AS 5S 9C").unwrap_err(),
+            HandParseError::DuplicateCard {
+                token: "AS".to_string(),
+                position: 2
+            }
+        )
+    }
+
+    #[test]
+    fn test_omaha_must_use_exactly_two_hole_cards() {
+        // four spades in hand and one on board is not a flush in Omaha
+        let best = omaha::best_hand("AS KS QS JS", "2S 7D 8C 9H 3D").unwrap();
+        assert_eq!(best.value().category(), HandRank::HighCard);
+        assert_eq!(best.value().ranks(), &[14, 13, 9, 8, 7])
+    }
+
+    #[test]
+    fn test_omaha_must_use_exactly_three_board_cards() {
+        // a four-flush on board needs two suited hole cards
+        let best = omaha::best_hand("AH KD 7C 7S", "2H 5H 9H 7H 3C").unwrap();
+        assert_eq!(best.value().category(), HandRank::ThreeOfKind);
+        assert_eq!(
+            best.cards(),
+            parse_cards("7C 7S 5H 9H 7H").unwrap().as_slice()
+        )
+    }
+
+    #[test]
+    fn test_omaha_accepts_five_hole_cards() {
+        let best = omaha::best_hand("AH KH 7C 7S 2D", "QH 5H 9H JS 3C").unwrap();
+        assert_eq!(best.value().description(), "Flush, Ace high")
+    }
+
+    #[test]
+    fn test_omaha_rejects_wrong_hole_card_count() {
+        assert!(matches!(
+            omaha::best_hand("AH KH 7C", "QH 5H 9H JS 3C"),
+            Err(HandParseError::WrongCardCount { count: 3, .. })
+        ))
+    }
+
+    #[test]
+    fn test_omaha_low_needs_eight_or_better() {
+        assert_eq!(
+            omaha::best_low("AS 2D KC KH", "3C 6D 8H QS JD")
+                .unwrap()
+                .unwrap()
+                .value()
+                .ranks(),
+            &[8, 6, 3, 2, 1]
+        );
+        assert_eq!(
+            omaha::best_low("AS 2D KC KH", "3C 9D 10H QS JD").unwrap(),
+            None
+        )
+    }
+
+    #[test]
+    fn test_omaha_hi_lo_splits_pot() {
+        let pot = omaha::hi_lo_showdown(
+            &["AS 2D KC KH", "QC QD 4S 5S", "3S 4D JH 10C"],
+            "3C 6D 8H KS JD",
+        )
+        .unwrap();
+        assert_eq!(pot.high, vec!["AS 2D KC KH"]);
+        assert_eq!(pot.low, vec!["AS 2D KC KH"])
+    }
+
+    #[test]
+    fn test_omaha_hi_lo_high_scoops_without_low() {
+        let pot =
+            omaha::hi_lo_showdown(&["AS KD KC QH", "QC QD JS 10S"], "3C 9D 10H KS JD").unwrap();
+        assert_eq!(pot.high, vec!["AS KD KC QH"]);
+        assert!(pot.low.is_empty())
+    }
+
+    #[test]
+    fn test_omaha_hi_lo_ties_split_the_low() {
+        let pot = omaha::hi_lo_showdown(&["AS 2D KC KH", "AH 2C QD QH"], "3C 6D 8H 9S JD").unwrap();
+        assert_eq!(pot.high, vec!["AS 2D KC KH"]);
+        assert_eq!(pot.low, vec!["AS 2D KC KH", "AH 2C QD QH"])
+    }
+
+    #[test]
+    fn test_ace_to_five_wheel_is_the_nuts() {
+        let value = evaluate_with("4D AH 3S 2D 5C", RankingRules::AceToFive);
+        assert_eq!(value.category(), HandRank::HighCard);
+        assert_eq!(value.ranks(), &[5, 4, 3, 2, 1]);
+        assert_eq!(value.description(), "Five-four low");
+        assert_eq!(
+            winning_hands_with(
+                &["4D AH 3S 2D 5C", "6S 4C 3H 2H AS"],
+                RankingRules::AceToFive
+            ),
+            Ok(vec!["4D AH 3S 2D 5C"])
+        )
+    }
+
+    #[test]
+    fn test_ace_to_five_ignores_flushes() {
+        assert_eq!(
+            winning_hands_with(
+                &["7H 5H 4H 3H 2H", "8S 5C 4D 3C AD"],
+                RankingRules::AceToFive
+            ),
+            Ok(vec!["7H 5H 4H 3H 2H"])
+        )
+    }
+
+    #[test]
+    fn test_ace_to_five_pair_loses_to_no_pair() {
+        assert_eq!(
+            winning_hands_with(
+                &["AS AD 2C 3H 4H", "KS QD JC 9H 8H"],
+                RankingRules::AceToFive
+            ),
+            Ok(vec!["KS QD JC 9H 8H"])
+        )
+    }
+
+    #[test]
+    fn test_deuce_to_seven_seven_five_is_the_nuts() {
+        assert_eq!(
+            winning_hands_with(
+                &["7D 5H 4S 3D 2C", "7S 6C 4H 3H 2S", "8C 5C 4C 3C 2D"],
+                RankingRules::DeuceToSeven
+            ),
+            Ok(vec!["7D 5H 4S 3D 2C"])
+        )
+    }
+
+    #[test]
+    fn test_deuce_to_seven_straights_and_flushes_count_against() {
+        assert_eq!(
+            winning_hands_with(
+                &["6D 5H 4S 3D 2C", "KS 9C 7H 3H 2S"],
+                RankingRules::DeuceToSeven
+            ),
+            Ok(vec!["KS 9C 7H 3H 2S"])
+        );
+        assert_eq!(
+            winning_hands_with(
+                &["8S 6S 4S 3S 2S", "9C 6C 4H 3H 2S"],
+                RankingRules::DeuceToSeven
+            ),
+            Ok(vec!["9C 6C 4H 3H 2S"])
+        )
+    }
+
+    #[test]
+    fn test_deuce_to_seven_ace_is_always_high() {
+        let value = evaluate_with("AD 5H 4S 3D 2C", RankingRules::DeuceToSeven);
+        assert_eq!(value.category(), HandRank::HighCard);
+        assert_eq!(value.ranks(), &[14, 5, 4, 3, 2]);
+        assert_eq!(
+            winning_hands_with(
+                &["AD 5H 4S 3D 2C", "KS QC JH 9H 8S"],
+                RankingRules::DeuceToSeven
+            ),
+            Ok(vec!["KS QC JH 9H 8S"])
+        )
+    }
+
+    #[test]
+    fn test_high_rules_match_winning_hands() {
+        let hands = &["4S 5H 4C 8D 4H", "10D JH QS KD AC"];
+        assert_eq!(
+            winning_hands_with(hands, RankingRules::High),
+            Ok(winning_hands(hands))
+        )
+    }
+
+    #[test]
+    fn test_joker_completes_the_best_hand() {
+        let value = evaluate("JK AS AH AD KC");
+        assert_eq!(value.category(), HandRank::FourOfKind);
+        assert_eq!(value.ranks(), &[14, 13]);
+
+        assert_eq!(evaluate("JK 10H JH QH KH").description(), "Royal flush");
+        assert_eq!(
+            evaluate("JK 2S 3D 4C 5H").description(),
+            "Straight, Six high"
+        )
+    }
+
+    #[test]
+    fn test_five_of_a_kind_beats_straight_flush() {
+        let value = evaluate("JK AS AH AD AC");
+        assert_eq!(value.category(), HandRank::FiveOfKind);
+        assert_eq!(value.description(), "Five of a kind, Aces");
+        assert_eq!(
+            winning_hands(&["9S 10S JS QS KS", "JK AS AH AD AC"]),
+            vec!["JK AS AH AD AC"]
+        )
+    }
+
+    #[test]
+    fn test_jokers_may_repeat_in_a_hand() {
+        let value = evaluate("JK JK 7S 7H 2D");
+        assert_eq!(value.category(), HandRank::FourOfKind);
+        assert_eq!(value.ranks(), &[7, 2])
+    }
+
+    #[test]
+    fn test_deuces_wild() {
+        let value = evaluate_wild("2S 2H KD KC 9S", RankingRules::High, &[2]);
+        assert_eq!(value.category(), HandRank::FourOfKind);
+        assert_eq!(value.ranks(), &[13, 9]);
+
+        assert_eq!(
+            winning_hands_wild(
+                &["2C 8S 8H 8D 3S", "AS KS QS JS 9S"],
+                RankingRules::High,
+                &[2]
+            ),
+            Ok(vec!["2C 8S 8H 8D 3S"])
+        )
+    }
+
+    #[test]
+    fn test_wild_card_breaks_flush_when_it_helps_in_lowball() {
+        let value = evaluate_wild("JK 7H 5H 4H 3H", RankingRules::DeuceToSeven, &[]);
+        assert_eq!(value.category(), HandRank::HighCard);
+        assert_eq!(value.ranks(), &[7, 5, 4, 3, 2])
+    }
+
+    #[test]
+    fn test_joker_is_the_lowest_missing_card_in_ace_to_five() {
+        let value = evaluate_with("JK 2D 3C 4S 5H", RankingRules::AceToFive);
+        assert_eq!(value.ranks(), &[5, 4, 3, 2, 1])
+    }
+
+    #[test]
+    fn test_deck_has_fifty_two_distinct_cards() {
+        let deck = Deck::new();
+        let distinct: HashSet<_> = deck.cards().iter().collect();
+        assert_eq!(deck.len(), 52);
+        assert_eq!(distinct.len(), 52)
+    }
+
+    #[test]
+    fn test_deck_remove_known_cards() {
+        let mut deck = Deck::new();
+        deck.remove(&parse_cards("AS KD 2C").unwrap());
+        assert_eq!(deck.len(), 49);
+        assert!(!deck.cards().contains(&parse_cards("KD").unwrap()[0]))
+    }
+
+    #[test]
+    fn test_equity_on_the_river_is_decided() {
+        let result = equity::equity(&["AS AH", "KS KH"], "2C 7D 9H QC 3S", "", 0).unwrap();
+        assert_eq!(result[0].win, 100.0);
+        assert_eq!(result[1].equity, 0.0)
+    }
+
+    #[test]
+    fn test_equity_on_the_turn_counts_outs() {
+        let result = equity::equity(&["AS AH", "KS KH"], "2C 7D 9H QC", "", 0).unwrap();
+        assert!((result[1].win - 100.0 * 2.0 / 44.0).abs() < 1e-9);
+        assert!((result[0].equity - 100.0 * 42.0 / 44.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_equity_respects_dead_cards() {
+        let result = equity::equity(&["AS AH", "KS KH"], "2C 7D 9H QC", "KC", 0).unwrap();
+        assert!((result[1].win - 100.0 / 43.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_equity_counts_split_pots() {
+        let result = equity::equity(&["AS KH", "AD KC"], "2C 7D 9H QS", "", 0).unwrap();
+        assert!(result[0].tie > 90.0);
+        assert!((result[0].equity + result[1].equity - 100.0).abs() < 1e-9);
+        assert_eq!(result[0].win, result[1].win)
+    }
+
+    #[test]
+    fn test_equity_monte_carlo_is_reproducible() {
+        let method = equity::Method::MonteCarlo {
+            trials: 2_000,
+            seed: 42,
+        };
+        let first = equity::calculate(&["AS AH", "KS KH"], "", "", method).unwrap();
+        let second = equity::calculate(&["AS AH", "KS KH"], "", "", method).unwrap();
+        assert_eq!(first, second);
+        assert!(first[0].equity > 75.0 && first[0].equity < 88.0)
+    }
+
+    #[test]
+    fn test_equity_rejects_shared_cards() {
+        assert_eq!(
+            equity::equity(&["AS AH", "AS KH"], "", "", 0).unwrap_err(),
+            equity::EquityError::Parse(HandParseError::DuplicateCard {
+                token: "AS".to_string(),
+                position: 0
+            })
+        )
+    }
+
+    #[test]
+    fn test_equity_rejects_more_players_than_the_deck_serves() {
+        let deck: Vec<String> = Deck::new().cards().iter().map(Card::to_string).collect();
+        let players: Vec<String> = deck.chunks(2).take(24).map(|hole| hole.join(" ")).collect();
+        let players: Vec<&str> = players.iter().map(String::as_str).collect();
+        let expected = equity::EquityError::NotEnoughCards { needed: 5, left: 4 };
+
+        assert_eq!(
+            equity::calculate(
+                &players,
+                "",
+                "",
+                equity::Method::MonteCarlo {
+                    trials: 10,
+                    seed: 0
+                }
+            )
+            .unwrap_err(),
+            expected
+        );
+        assert_eq!(
+            equity::calculate(&players, "", "", equity::Method::Exhaustive).unwrap_err(),
+            expected.clone()
+        );
+
+        let ranges: Vec<String> = players.iter().map(|hole| hole.replace(' ', "")).collect();
+        let ranges: Vec<&str> = ranges.iter().map(String::as_str).collect();
+        assert_eq!(
+            range::equity(&ranges, "", "", 0),
+            Err(RangeError::NotEnoughCards { needed: 5, left: 4 })
+        )
+    }
+
+    fn packed(cards: &str) -> Vec<PackedCard> {
+        parse_cards(cards)
+            .unwrap()
+            .into_iter()
+            .map(|card| PackedCard::try_from(card).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_lookup_has_every_hand_class() {
+        assert_eq!(lookup::classes(), 7462)
+    }
+
+    #[test]
+    fn test_lookup_strength_maps_back_to_value() {
+        let hand = packed("KS 4H KC 4D KH");
+        let strength = lookup::evaluate(&[hand[0], hand[1], hand[2], hand[3], hand[4]]);
+        assert_eq!(lookup::value(strength), Some(&evaluate("KS 4H KC 4D KH")))
+    }
+
+    #[test]
+    fn test_lookup_best_of_seven() {
+        let strength = lookup::best(&packed("AH 7C KH 2H 9H 7D QH")).unwrap();
+        assert_eq!(
+            lookup::value(strength).unwrap().description(),
+            "Flush, Ace high"
+        )
+    }
+
+    #[test]
+    fn test_lookup_needs_five_cards() {
+        assert_eq!(lookup::best(&packed("AH 7C KH 2H")), None);
+        assert_eq!(lookup::value(0), None);
+        assert_eq!(lookup::value(7463), None)
+    }
+
+    #[test]
+    fn test_lookup_matches_evaluator_on_shuffled_hands() {
+        for seed in 0..2000 {
+            let mut deck = Deck::new();
+            deck.shuffle(seed);
+
+            let cards = &deck.cards()[..5];
+            let packed: Vec<PackedCard> = cards
+                .iter()
+                .map(|&card| PackedCard::try_from(card).unwrap())
+                .collect();
+            let strength =
+                lookup::evaluate(&[packed[0], packed[1], packed[2], packed[3], packed[4]]);
+
+            assert_eq!(lookup::value(strength), Some(&evaluate(&joined(cards))))
+        }
+    }
+
+    #[test]
+    fn test_lookup_rejects_jokers() {
+        let joker = parse_cards("JK").unwrap()[0];
+        assert_eq!(PackedCard::try_from(joker), Err(joker))
+    }
+
+    #[test]
+    #[ignore = "slow, run with --ignored"]
+    fn test_lookup_matches_evaluator_on_every_hand() {
+        let cards = Deck::new().cards().to_vec();
+        let packed: Vec<PackedCard> = cards
+            .iter()
+            .map(|&card| PackedCard::try_from(card).unwrap())
+            .collect();
+        let mut count = 0;
+
+        for a in 0..52 {
+            for b in a + 1..52 {
+                for c in b + 1..52 {
+                    for d in c + 1..52 {
+                        for e in d + 1..52 {
+                            let strength = lookup::evaluate(&[
+                                packed[a], packed[b], packed[c], packed[d], packed[e],
+                            ]);
+                            let value = evaluate(&joined(&[
+                                cards[a], cards[b], cards[c], cards[d], cards[e],
+                            ]));
+
+                            assert_eq!(lookup::value(strength), Some(&value));
+                            count += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_eq!(count, 2_598_960)
+    }
+
+    fn joined(cards: &[Card]) -> String {
+        cards
+            .iter()
+            .map(|card| card.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_card_display_round_trips() {
+        let cards = parse_cards("10H QS 2C AD JK").unwrap();
+        assert_eq!(joined(&cards), "Th Qs 2c Ad JK");
+        assert_eq!(parse_cards(&joined(&cards)).unwrap(), cards)
+    }
+
+    #[test]
+    fn test_deck_shuffle_is_seeded() {
+        let (mut first, mut second, mut other) = (Deck::new(), Deck::new(), Deck::new());
+        first.shuffle(7);
+        second.shuffle(7);
+        other.shuffle(8);
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert_ne!(first, Deck::new());
+        assert_eq!(first.len(), 52)
+    }
+
+    #[test]
+    fn test_deck_deals_round_the_table() {
+        let mut deck = Deck::new();
+        let top: Vec<Card> = deck.cards().iter().rev().take(4).copied().collect();
+        let seats = deck.deal_seats(2, 2).unwrap();
+
+        assert_eq!(seats[0], vec![top[0], top[2]]);
+        assert_eq!(seats[1], vec![top[1], top[3]]);
+        assert_eq!(deck.len(), 48)
+    }
+
+    #[test]
+    fn test_deck_deals_streets_with_burns() {
+        let mut deck = Deck::new();
+        deck.shuffle(1);
+        deck.deal_seats(6, 2).unwrap();
+
+        assert_eq!(deck.deal_street(Street::Flop).unwrap().len(), 3);
+        assert_eq!(deck.deal_street(Street::Turn).unwrap().len(), 1);
+        assert_eq!(deck.deal_street(Street::River).unwrap().len(), 1);
+        assert_eq!(deck.len(), 52 - 12 - 8)
+    }
+
+    #[test]
+    fn test_deck_refuses_to_deal_past_the_end() {
+        let mut deck = Deck::new();
+        assert_eq!(deck.deal_seats(11, 5), None);
+        assert_eq!(deck.len(), 52);
+
+        deck.deal_seats(10, 5).unwrap();
+        assert_eq!(deck.deal_street(Street::Flop), None);
+        assert_eq!(deck.len(), 2)
+    }
+
+    #[test]
+    fn test_short_deck_starts_at_six() {
+        let deck = Deck::short();
+        assert_eq!(deck.len(), 36);
+        assert!(deck.cards().iter().all(|card| card.value() >= 6))
+    }
+
+    #[test]
+    fn test_seeded_deal_feeds_winning_hands() {
+        let deal = || {
+            let mut deck = Deck::new();
+            deck.shuffle(2024);
+            deck.deal_seats(4, 5)
+                .unwrap()
+                .iter()
+                .map(|hand| joined(hand))
+                .collect::<Vec<_>>()
+        };
+
+        let hands = deal();
+        let hands: Vec<&str> = hands.iter().map(|hand| hand.as_str()).collect();
+        let winners = try_winning_hands(&hands).unwrap();
+
+        assert!(!winners.is_empty());
+        assert_eq!(deal(), hands)
+    }
+
+    const HISTORY: &str =
+        "PokerStars Hand #2401: Hold'em No Limit ($0.50/$1.00 USD) - 2023/01/01 12:00:00 ET
+Table 'Alpha' 6-max Seat #1 is the button
+Seat 1: Alice ($100 in chips)
+Seat 2: Bob ($80.50 in chips)
+Seat 3: Carol ($120 in chips)
+Bob: posts small blind $0.50
+Carol: posts big blind $1
+*** HOLE CARDS ***
+Dealt to Alice [Ah Kd]
+Alice: raises $2 to $3
+Bob: calls $2.50
+Carol: folds
+*** FLOP *** [2c 7d Kh]
+Bob: checks
+Alice: bets $4
+Bob: calls $4
+*** TURN *** [2c 7d Kh] [Qc]
+Bob: checks
+Alice: bets $10
+Bob: calls $10
+*** RIVER *** [2c 7d Kh Qc] [Ts]
+Bob: bets $20
+Alice: calls $20
+*** SHOW DOWN ***
+Bob: shows [Qs Qh] (three of a kind, Queens)
+Alice: shows [Ah Kd] (a pair of Kings)
+Bob collected $73 from pot
+*** SUMMARY ***
+Total pot $75 | Rake $2
+Board [2c 7d Kh Qc Ts]
+Seat 1: Alice (button) showed [Ah Kd] and lost with a pair of Kings
+Seat 2: Bob (small blind) showed [Qs Qh] and won ($73) with three of a kind, Queens
+Seat 3: Carol (big blind) folded before Flop";
+
+    #[test]
+    fn test_history_parses_record() {
+        let hand = history::parse(HISTORY).unwrap();
+
+        assert_eq!(hand.id, "2401");
+        assert_eq!(hand.game, "Hold'em No Limit ($0.50/$1.00 USD)");
+        assert_eq!(hand.button, Some(1));
+        assert_eq!(hand.seats.len(), 3);
+        assert_eq!(hand.seats[1].stack, 8050);
+        assert_eq!(hand.board, parse_cards("2C 7D KH QC 10S").unwrap());
+        assert_eq!(
+            hand.dealt,
+            vec![("Alice".to_string(), parse_cards("AH KD").unwrap())]
+        );
+        assert_eq!(hand.shown.len(), 2);
+        assert_eq!(hand.collected, vec![("Bob".to_string(), 7300)]);
+        assert_eq!(hand.total_pot, Some(7500));
+        assert_eq!(hand.rake, 200);
+        assert_eq!(
+            hand.actions[3],
+            history::Action {
+                round: history::Round::Preflop,
+                player: "Bob".to_string(),
+                kind: history::ActionKind::Call(250),
+            }
+        )
+    }
+
+    #[test]
+    fn test_history_replay_agrees() {
+        let replay = history::parse(HISTORY).unwrap().replay();
+
+        assert_eq!(replay.pot, 7500);
+        assert_eq!(replay.winners, vec!["Bob"]);
+        assert!(replay.mismatches.is_empty())
+    }
+
+    #[test]
+    fn test_history_replay_reports_wrong_winner() {
+        let text = HISTORY.replace("Bob collected $73", "Alice collected $73");
+        let replay = history::parse(&text).unwrap().replay();
+
+        assert_eq!(
+            replay.mismatches,
+            vec![history::Mismatch::Winner {
+                declared: vec!["Alice".to_string()],
+                expected: vec!["Bob".to_string()],
+            }]
+        )
+    }
+
+    #[test]
+    fn test_history_replay_reports_pot_mismatch() {
+        let text = HISTORY.replace("Bob collected $73", "Bob collected $83");
+        let replay = history::parse(&text).unwrap().replay();
+
+        assert_eq!(
+            replay.mismatches,
+            vec![history::Mismatch::Pot {
+                declared: 8500,
+                computed: 7500,
+            }]
+        )
+    }
+
+    #[test]
+    fn test_history_replay_reports_duplicate_cards() {
+        let text = HISTORY.replace("Bob: shows [Qs Qh]", "Bob: shows [2h 2c]");
+        let replay = history::parse(&text).unwrap().replay();
+
+        assert_eq!(
+            replay.mismatches,
+            vec![history::Mismatch::Duplicates(vec![Duplicate {
+                card: parse_cards("2C").unwrap()[0],
+                holders: vec![Holder::Hand(1), Holder::Board],
+            }])]
+        )
+    }
+
+    #[test]
+    fn test_history_uncontested_pot() {
+        let text = "PokerStars Hand #7: Hold'em No Limit ($1/$2) - 2023/01/01
+Seat 1: Alice ($200 in chips)
+Seat 2: Bob ($200 in chips)
+Alice: posts small blind $1
+Bob: posts big blind $2
+*** HOLE CARDS ***
+Alice: raises $4 to $6
+Bob: folds
+Uncalled bet ($4) returned to Alice
+Alice collected $4 from pot
+Alice: doesn't show hand
+*** SUMMARY ***
+Total pot $4 | Rake $0";
+        let replay = history::parse(text).unwrap().replay();
+
+        assert_eq!(replay.pot, 400);
+        assert_eq!(replay.winners, vec!["Alice"]);
+        assert!(replay.mismatches.is_empty())
+    }
+
+    #[test]
+    fn test_history_rejects_unknown_line() {
+        let text = HISTORY.replace("Carol: folds", "Carol: dances");
+
+        assert_eq!(
+            history::parse(&text).unwrap_err(),
+            history::HistoryError::UnknownLine {
+                line: 12,
+                text: "Carol: dances".to_string()
+            }
+        )
+    }
+
+    #[test]
+    fn test_history_rejects_amount_too_large() {
+        let text = HISTORY.replace("($100 in chips)", "($999999999999999999 in chips)");
+
+        assert_eq!(
+            history::parse(&text).unwrap_err(),
+            history::HistoryError::InvalidAmount {
+                line: 3,
+                text: "$999999999999999999".to_string()
+            }
+        )
+    }
+
+    #[test]
+    fn test_history_rejects_bad_cards_and_players() {
+        assert!(matches!(
+            history::parse(&HISTORY.replace("[Ah Kd]", "[Ah Kx]")),
+            Err(history::HistoryError::InvalidCards { line: 9, .. })
+        ));
+        assert_eq!(
+            history::parse(&HISTORY.replace("Dealt to Alice", "Dealt to Dave")).unwrap_err(),
+            history::HistoryError::UnknownPlayer {
+                line: 9,
+                player: "Dave".to_string()
+            }
+        );
+        assert_eq!(
+            history::parse("not a hand history").unwrap_err(),
+            history::HistoryError::MissingHeader
+        )
+    }
+
+    fn seat(hand: &str, contribution: u64, status: Status) -> Player {
+        Player::new(Some(evaluate(hand)), contribution, status)
+    }
+
+    #[test]
+    fn test_showdown_builds_side_pot() {
+        let players = [
+            seat("AS AH AD 2C 3C", 50, Status::AllIn),
+            seat("KS KH 7D 2D 3D", 100, Status::Active),
+            seat("QS QH 7C 4D 5D", 100, Status::Active),
+        ];
+        let result = showdown::resolve(&players, 0).unwrap();
+
+        assert_eq!(
+            result.pots,
+            vec![
+                Pot {
+                    amount: 150,
+                    eligible: vec![0, 1, 2],
+                    winners: vec![0]
+                },
+                Pot {
+                    amount: 100,
+                    eligible: vec![1, 2],
+                    winners: vec![1]
+                },
+            ]
+        );
+        assert_eq!(result.payouts, vec![150, 100, 0])
+    }
+
+    #[test]
+    fn test_showdown_folded_chips_stay_in_the_pot() {
+        let players = [
+            Player::new(None, 30, Status::Folded),
+            seat("KS KH 7D 2D 3D", 60, Status::Active),
+            seat("QS QH 7C 4D 5D", 60, Status::Active),
+        ];
+        let result = showdown::resolve(&players, 0).unwrap();
+
+        assert_eq!(result.pots.len(), 1);
+        assert_eq!(result.payouts, vec![0, 150, 0])
+    }
+
+    #[test]
+    fn test_showdown_returns_uncalled_chips() {
+        let players = [
+            seat("2S 4H 6D 8C 10C", 150, Status::Active),
+            seat("AS AH 7D 2D 3D", 100, Status::AllIn),
+        ];
+        let result = showdown::resolve(&players, 0).unwrap();
+
+        assert_eq!(result.pots[1].eligible, vec![0]);
+        assert_eq!(result.payouts, vec![50, 200])
+    }
+
+    #[test]
+    fn test_showdown_odd_chip_goes_to_configured_seat() {
+        let players = [
+            seat("AS KH QD JC 9C", 34, Status::Active),
+            seat("AD KC QH JD 9D", 34, Status::Active),
+            Player::new(None, 33, Status::Folded),
+        ];
+
+        assert_eq!(
+            showdown::resolve(&players, 0).unwrap().payouts,
+            vec![51, 50, 0]
+        );
+        assert_eq!(
+            showdown::resolve(&players, 1).unwrap().payouts,
+            vec![50, 51, 0]
+        );
+        assert_eq!(
+            showdown::resolve(&players, 2).unwrap().payouts,
+            vec![51, 50, 0]
+        )
+    }
+
+    #[test]
+    fn test_showdown_uncontested_pot_needs_no_hand() {
+        let players = [
+            Player::new(None, 2, Status::Folded),
+            Player::new(None, 6, Status::Active),
+        ];
+
+        assert_eq!(showdown::resolve(&players, 0).unwrap().payouts, vec![0, 8])
+    }
+
+    #[test]
+    fn test_showdown_errors() {
+        let players = [
+            Player::new(None, 10, Status::Active),
+            seat("AS KH QD JC 9C", 10, Status::Active),
+        ];
+
+        assert_eq!(
+            showdown::resolve(&players, 0),
+            Err(ShowdownError::MissingHand { seat: 0 })
+        );
+        assert_eq!(
+            showdown::resolve(&players, 2),
+            Err(ShowdownError::InvalidSeat { seat: 2 })
+        );
+        assert_eq!(
+            showdown::resolve(&[Player::new(None, 10, Status::Folded)], 0),
+            Err(ShowdownError::NoLivePlayers)
+        )
+    }
+
+    #[test]
+    fn test_parse_accepts_alternate_notations() {
+        let expected = parse_cards("10H QS 2C AD JK").unwrap();
+
+        assert_eq!(parse_cards("TH QS 2C AD JK").unwrap(), expected);
+        assert_eq!(parse_cards("th qs 2c ad jk").unwrap(), expected);
+        assert_eq!(parse_cards("Th Qs 2c Ad Jk").unwrap(), expected);
+        assert_eq!(parse_cards("10♥ Q♠ 2♣ A♦ 🃏").unwrap(), expected);
+        assert_eq!(parse_cards("T♡ Q♤ 2♧ A♢ JK").unwrap(), expected)
+    }
+
+    #[test]
+    fn test_parse_error_keeps_original_token() {
+        assert_eq!(
+            parse_hand("4s 5s 7h 8d jx").unwrap_err(),
+            HandParseError::InvalidSuit {
+                token: "jx".to_string(),
+                position: 4
+            }
+        )
+    }
+
+    #[test]
+    fn test_glyph_hands_win_like_ascii_hands() {
+        test(&["4♠ 5♥ 4♣ 8♦ 4♥", "t♦ j♥ q♠ k♦ a♣"], &["t♦ j♥ q♠ k♦ a♣"])
+    }
+
+    #[test]
+    fn test_card_display_with_glyphs() {
+        let cards = parse_cards("10H QS 2C AD JK").unwrap();
+        let glyphs: Vec<String> = cards.iter().map(|card| format!("{:#}", card)).collect();
+
+        assert_eq!(glyphs.join(" "), "T♥ Q♠ 2♣ A♦ 🃏");
+        assert_eq!(parse_cards(&glyphs.join(" ")).unwrap(), cards)
+    }
+
+    #[test]
+    fn test_hand_display() {
+        let hand = parse_hand("AH 10D 2C KS 9S").unwrap();
+        assert_eq!(hand.to_string(), "Ah Td 2c Ks 9s");
+        assert_eq!(format!("{:#}", hand), "A♥ T♦ 2♣ K♠ 9♠");
+
+        let best = holdem::best_hand("AH 7C", "KH 2H 9H 7D QH").unwrap();
+        assert_eq!(best.to_string(), "Ah Kh 2h 9h Qh")
+    }
+
+    #[test]
+    fn test_find_duplicates_reports_every_holder() {
+        let duplicates =
+            validate::find_duplicates(&["AS KD", "AS QH", "2C 3C"], Some("KD AS 7H 8H 9H"))
+                .unwrap();
+
+        assert_eq!(
+            duplicates,
+            vec![
+                Duplicate {
+                    card: parse_cards("AS").unwrap()[0],
+                    holders: vec![Holder::Hand(0), Holder::Hand(1), Holder::Board],
+                },
+                Duplicate {
+                    card: parse_cards("KD").unwrap()[0],
+                    holders: vec![Holder::Hand(0), Holder::Board],
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn test_find_duplicates_ignores_jokers_and_clean_deals() {
+        assert_eq!(
+            validate::find_duplicates(&["JK AS", "JK KS"], Some("2C 3C 4C")),
+            Ok(vec![])
+        )
+    }
+
+    #[test]
+    fn test_winning_hands_strict_refuses_shared_cards() {
+        let hands = &["3S 4S 5D 6H JH", "3S 4H 5C 6C JD"];
+        let err = winning_hands_strict(hands).unwrap_err();
+
+        assert_eq!(
+            err,
+            DealError::Duplicates(vec![Duplicate {
+                card: parse_cards("3S").unwrap()[0],
+                holders: vec![Holder::Hand(0), Holder::Hand(1)],
+            }])
+        );
+        assert_eq!(err.to_string(), "3s held by hand 0, hand 1");
+        assert_eq!(winning_hands(hands), hands.to_vec())
+    }
+
+    #[test]
+    fn test_winning_hands_strict_accepts_a_clean_deal() {
+        assert_eq!(
+            winning_hands_strict(&["4S 5H 4C 8D 4H", "10D JH QS KD AC"]),
+            Ok(vec!["10D JH QS KD AC"])
+        );
+        assert!(matches!(
+            winning_hands_strict(&["4S 5H 4C 8D"]),
+            Err(DealError::Parse(HandParseError::WrongCardCount { .. }))
+        ))
+    }
+
+    #[test]
+    fn test_range_expands_notation() {
+        let count = |notation: &str| range::parse(notation).unwrap().len();
+
+        assert_eq!(count("QQ+"), 18);
+        assert_eq!(count("AKs"), 4);
+        assert_eq!(count("A5s-A2s"), 16);
+        assert_eq!(count("KQo"), 12);
+        assert_eq!(count("AK"), 16);
+        assert_eq!(count("99-77"), 18);
+        assert_eq!(count("A9s+"), 20);
+        assert_eq!(count("QQ+, AKs, A5s-A2s, KQo"), 50)
+    }
+
+    #[test]
+    fn test_range_counts_overlaps_and_exact_combos_once() {
+        let range = range::parse("AhKh, AKs, KhAh").unwrap();
+
+        assert_eq!(range.len(), 4);
+        assert!(range
+            .combos()
+            .contains(&[parse_cards("AH").unwrap()[0], parse_cards("KH").unwrap()[0]]));
+
+        assert_eq!(range::parse("AA, AcAd").unwrap().len(), 6);
+        assert_eq!(range::parse("AdAc, AA").unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_range_card_removal() {
+        let mut range = range::parse("AA, AKs").unwrap();
+        range.remove(&parse_cards("AH KS").unwrap());
+
+        assert_eq!(range.len(), 3 + 2)
+    }
+
+    #[test]
+    fn test_range_rejects_bad_tokens() {
+        for token in ["AAs", "A5s-K2s", "QQ-A5s", "XY", "AKx", "AhAh"] {
+            assert_eq!(
+                range::parse(&format!("KK, {}", token)),
+                Err(RangeError::InvalidToken {
+                    token: token.to_string()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_range_equity_weights_combinations() {
+        // Three sets of deuces win against queens, four suited AK lose.
+        let result = range::equity(&["AKs, 22", "QQ"], "2C 7D 8H JS 3C", "", 0).unwrap();
+
+        assert!((result[0].equity - 100.0 * 18.0 / 42.0).abs() < 1e-9);
+        assert!((result[1].equity - 100.0 * 24.0 / 42.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_range_equity_matches_single_hands() {
+        let hands = equity::equity(&["AS AH", "KS KH"], "2C 7D 9H QC", "", 0).unwrap();
+        let ranges = range::equity(&["AsAh", "KsKh"], "2C 7D 9H QC", "", 0).unwrap();
+
+        assert_eq!(hands, ranges)
+    }
+
+    #[test]
+    fn test_range_equity_preflop_is_sampled() {
+        let result = range::equity(&["AA", "KK"], "", "", 7).unwrap();
+
+        assert!((result[0].equity - 82.0).abs() < 2.0);
+        assert_eq!(result, range::equity(&["AA", "KK"], "", "", 7).unwrap())
+    }
+
+    #[test]
+    fn test_range_equity_errors() {
+        assert_eq!(
+            range::equity(&["AhAs", "AhAs"], "", "", 0),
+            Err(RangeError::NoDeal)
+        );
+        assert_eq!(
+            range::equity(&["KK", "AsKs"], "AS 2C 3D", "", 0),
+            Err(RangeError::EmptyRange { player: 1 })
+        );
+        assert!(matches!(
+            range::equity(&["KK", "QQ"], "AS 2C 3D", "2C", 0),
+            Err(RangeError::Cards(HandParseError::DuplicateCard { .. }))
+        ))
+    }
+
+    #[test]
+    fn test_short_deck_flush_beats_full_house() {
+        assert_eq!(
+            winning_hands_with(
+                &["KS KH KD 6C 6S", "9H 7H JH QH 6H"],
+                RankingRules::ShortDeck
+            ),
+            Ok(vec!["9H 7H JH QH 6H"])
+        );
+        assert_eq!(
+            winning_hands(&["KS KH KD 6C 6S", "9H 7H JH QH 6H"]),
+            vec!["KS KH KD 6C 6S"]
+        )
+    }
+
+    #[test]
+    fn test_short_deck_ace_six_straight() {
+        let value = evaluate_with("AD 6H 7S 8D 9C", RankingRules::ShortDeck);
+
+        assert_eq!(value.category(), HandRank::Straight);
+        assert_eq!(value.ranks(), &[9]);
+        assert_eq!(value.to_string(), "Straight, Nine high");
+        assert_eq!(
+            winning_hands_with(
+                &["AD 6H 7S 8D 9C", "AS AC KH QD JC", "6D 7D 8H 9S 10C"],
+                RankingRules::ShortDeck
+            ),
+            Ok(vec!["6D 7D 8H 9S 10C"])
+        );
+        assert_eq!(
+            evaluate_with("AH 6H 7H 8H 9H", RankingRules::ShortDeck).category(),
+            HandRank::StraightFlush
+        );
+    }
+
+    #[test]
+    fn test_short_deck_refuses_cards_below_six() {
+        let five = HandParseError::InvalidRank {
+            token: "5H".to_string(),
+            position: 1,
+        };
+
+        assert_eq!(
+            winning_hands_with(
+                &["AS KS QS JS 9S", "AD 5H 4S 3D 2C"],
+                RankingRules::ShortDeck
+            ),
+            Err(five.clone())
+        );
+        assert_eq!(
+            parse_hand_with("AD 5H 4S 3D 2C", RankingRules::ShortDeck).unwrap_err(),
+            five
+        );
+        assert!(parse_hand_with("AD 5H 4S 3D 2C", RankingRules::High).is_ok());
+        assert!(parse_hand_with("JK 6H 7S 8D 9C", RankingRules::ShortDeck).is_ok())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_short_deck_evaluate_panics_on_cards_below_six() {
+        evaluate_with("AD 5H 4S 3D 2C", RankingRules::ShortDeck);
+    }
+
+    #[test]
+    fn test_short_deck_holdem_showdown() {
+        let players = ["KS KH", "9H 7H"];
+        let board = "KD QH JH 6H 6S";
+
+        assert_eq!(
+            holdem::showdown_with(&players, board, RankingRules::ShortDeck),
+            Ok(vec!["9H 7H"])
+        );
+        assert_eq!(holdem::showdown(&players, board), Ok(vec!["KS KH"]));
+        assert_eq!(
+            holdem::best_hand_with("9H 7H", board, RankingRules::ShortDeck)
+                .unwrap()
+                .value()
+                .category(),
+            HandRank::Flush
+        );
+        assert_eq!(
+            holdem::best_hand_with("9H 2H", board, RankingRules::ShortDeck),
+            Err(HandParseError::InvalidRank {
+                token: "2H".to_string(),
+                position: 1
+            })
+        )
+    }
+
+    #[test]
+    fn test_short_deck_equity() {
+        let players = ["KS KH", "9H 7H"];
+
+        let short = equity::equity_with(&players, "KD QH JH 6H 6S", "", 0, RankingRules::ShortDeck)
+            .unwrap();
+        assert_eq!(short[1].win, 100.0);
+
+        let high = equity::equity(&players, "KD QH JH 6H 6S", "", 0).unwrap();
+        assert_eq!(high[0].win, 100.0);
+
+        // On the turn, only the 36-card deck is dealt: 28 rivers are left.
+        let turn = equity::calculate_with(
+            &players,
+            "KD QH JH 6S",
+            "",
+            equity::Method::Exhaustive,
+            RankingRules::ShortDeck,
+        )
+        .unwrap();
+        let rivers = turn[0].equity * 28.0 / 100.0;
+        assert!((rivers - rivers.round()).abs() < 1e-9);
+
+        assert_eq!(
+            equity::equity_with(&players, "", "2C", 0, RankingRules::ShortDeck).unwrap_err(),
+            equity::EquityError::Parse(HandParseError::InvalidRank {
+                token: "2C".to_string(),
+                position: 0
+            })
+        )
+    }
+
+    #[test]
+    fn test_short_deck_wild_cards_stay_in_the_deck() {
+        let value = evaluate_wild("JK 6H 7S 8D 9C", RankingRules::ShortDeck, &[]);
+
+        assert_eq!(value.category(), HandRank::Straight);
+        assert_eq!(value.ranks(), &[10]);
+        assert_eq!(
+            evaluate_wild("JK 7H 7S 7D 6C", RankingRules::ShortDeck, &[]).category(),
+            HandRank::FourOfKind
+        )
+    }
+
+    #[test]
+    fn test_kickers_are_the_ranks_left_out_of_the_description() {
+        assert_eq!(evaluate("KS KH 9D 4C 2S").kickers(), &[9, 4, 2]);
+        assert_eq!(evaluate("KS KH 9D 9C 2S").kickers(), &[2]);
+        assert_eq!(evaluate("KS KH KD 9C 9S").kickers(), &[] as &[u8]);
+        assert_eq!(evaluate("10D JH QS KD AC").kickers(), &[] as &[u8]);
+        assert_eq!(
+            evaluate_with("8D 5H 4S 3D 2C", RankingRules::AceToFive).kickers(),
+            &[4, 3, 2]
+        )
+    }
+
+    fn run_cli(args: &[&str], input: &str) -> (i32, String, String) {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new(env!("CARGO_BIN_EXE_poker"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+
+        let output = child.wait_with_output().unwrap();
+
+        (
+            output.status.code().unwrap(),
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_cli_prints_winners_of_each_showdown() {
+        let input = "KS KH 9D 7C 2S\nKD KC 8D 7H 2H\n\n3S 4S 5D 6H JH\n3H 4H 5C 6C JD\n";
+        let (status, stdout, _) = run_cli(&[], input);
+
+        assert_eq!(status, 0);
+        assert_eq!(
+            stdout,
+            "Showdown 1 (line 1): winner\n  \
+             KS KH 9D 7C 2S: One pair, Kings, kickers Nine, Seven, Two; \
+             wins on kicker Nine against Eight\n\
+             Showdown 2 (line 4): split pot\n  \
+             3S 4S 5D 6H JH: High card, Jack, kickers Six, Five, Four, Three\n  \
+             3H 4H 5C 6C JD: High card, Jack, kickers Six, Five, Four, Three\n"
+        )
+    }
+
+    #[test]
+    fn test_cli_json_output() {
+        let (status, stdout, _) = run_cli(
+            &["--json", "--rules", "short-deck"],
+            "KS KH KD 6C 6S\n9H 7H JH QH 6H\n",
+        );
+
+        assert_eq!(status, 0);
+        assert_eq!(
+            stdout,
+            "{\"showdowns\":[{\"line\":1,\"split\":false,\"winners\":[{\"hand\":\"9H 7H JH QH 6H\",\
+             \"category\":\"Flush\",\"description\":\"Flush, Queen high\",\
+             \"kickers\":[\"Jack\",\"Nine\",\"Seven\",\"Six\"],\"deciding_kicker\":null}]}]}\n"
+        )
+    }
+
+    #[test]
+    fn test_cli_fails_on_invalid_input() {
+        let (status, stdout, stderr) = run_cli(&[], "KS KH 9D 7C 2S\nKS KC 8D 7H\n");
+
+        assert_eq!(status, 1);
+        assert_eq!(stdout, "");
+        assert!(stderr.contains("line 2"));
+
+        let (status, _, stderr) = run_cli(&[], "KS KH 9D 7C 2S\nKS KC 8D 7H 2H\n");
+
+        assert_eq!(status, 1);
+        assert!(stderr.contains("Ks is dealt on lines 1, 2"));
+        assert_eq!(run_cli(&["--rules", "razz"], "").0, 2)
+    }
 }